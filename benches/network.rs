@@ -0,0 +1,141 @@
+use std::{hint::black_box, time::Instant};
+
+use mentor::network::{
+    activation::{ReLu, TanH},
+    layer::{DenseLayer, Layer},
+    quantized::Quantized,
+    simd,
+    vector::Vector,
+};
+
+const ITERATIONS: usize = 200_000;
+
+fn bench<F: FnMut()>(name: &str, iterations: usize, mut f: F) {
+    let timer = Instant::now();
+
+    for _ in 0..iterations {
+        f();
+    }
+
+    let elapsed = timer.elapsed();
+    println!(
+        "{:<24} {:>10.1} ns/iter",
+        name,
+        elapsed.as_nanos() as f64 / iterations as f64
+    );
+}
+
+fn random_layer<const N: usize, const M: usize>(seed: usize) -> Box<DenseLayer<f32, N, M>> {
+    let scale = 1.0 / (N as f32).sqrt();
+
+    let mut bytes = Vec::with_capacity(4 * (N + 1) * M);
+    for i in 0..(N + 1) * M {
+        let weight = ((i * 7919 + seed * 104_729) as f32 * 0.618).sin() * scale;
+        bytes.extend_from_slice(&weight.to_le_bytes());
+    }
+
+    DenseLayer::read(&mut bytes.as_slice()).unwrap()
+}
+
+fn sparse_inputs<const N: usize>(count: usize) -> Vec<Vector<f32, N>> {
+    (0..count)
+        .map(|i| {
+            Vector(std::array::from_fn(|j| {
+                f32::from((i * 31 + j * 17) % 11 == 0)
+            }))
+        })
+        .collect()
+}
+
+/// Compares a two hidden layer network against its quantized counterparts.
+fn bench_network<const N: usize, const H: usize>(name: &str) {
+    let l1 = random_layer::<N, H>(1);
+    let l2 = random_layer::<H, 1>(2);
+
+    let inputs = sparse_inputs::<N>(64);
+    let iterations = 2_000_000 / N;
+
+    let float = |input: &Vector<f32, N>| l2.forward::<TanH>(l1.forward::<ReLu>(*input)).0[0];
+    bench(&format!("{} f32", name), iterations, || {
+        for input in &inputs {
+            black_box(float(black_box(input)));
+        }
+    });
+
+    fn quantized<T: Quantized, const N: usize, const H: usize>(
+        name: &str,
+        iterations: usize,
+        l1: &DenseLayer<f32, N, H>,
+        l2: &DenseLayer<f32, H, 1>,
+        inputs: &[Vector<f32, N>],
+        float: impl Fn(&Vector<f32, N>) -> f32,
+    ) {
+        let (q1, q2) = (l1.quantize::<T>(), l2.quantize::<T>());
+        let eval = |input: &Vector<f32, N>| {
+            q2.forward_f32::<TanH>(&q1.forward::<ReLu>(input.quantize()))
+                .0[0]
+        };
+
+        bench(name, iterations, || {
+            for input in inputs {
+                black_box(eval(black_box(input)));
+            }
+        });
+
+        let error = inputs
+            .iter()
+            .map(|input| (eval(input) - float(input)).abs())
+            .fold(0f32, f32::max);
+        println!("{:<24} {:>10.5} max abs error", name, error);
+    }
+
+    quantized::<i16, N, H>(
+        &format!("{} i16", name),
+        iterations,
+        &l1,
+        &l2,
+        &inputs,
+        float,
+    );
+    quantized::<i8, N, H>(
+        &format!("{} i8", name),
+        iterations,
+        &l1,
+        &l2,
+        &inputs,
+        float,
+    );
+}
+
+fn main() {
+    let a: Vec<f32> = (0..768).map(|i| (i as f32).sin()).collect();
+    let b: Vec<f32> = (0..768).map(|i| (i as f32).cos()).collect();
+
+    for (name, kernel) in simd::available_kernels() {
+        bench(&format!("dot768 {}", name), ITERATIONS, || {
+            black_box(kernel(black_box(&a), black_box(&b)));
+        });
+    }
+
+    let weights: Box<[Vector<f32, 256>; 64]> = Box::new(std::array::from_fn(|i| {
+        Vector(std::array::from_fn(|j| ((i * 256 + j) as f32).sin()))
+    }));
+    let bias = Vector(std::array::from_fn(|i| (i as f32).cos()));
+    let input = Vector(std::array::from_fn(|i| (i as f32 * 0.37).sin()));
+
+    let layer = DenseLayer::new(*weights, bias);
+    bench("dense256x64 forward", ITERATIONS / 10, || {
+        black_box(layer.forward::<ReLu>(black_box(input)));
+    });
+
+    bench("dense256x64 scalar", ITERATIONS / 10, || {
+        let mut output = bias;
+        for (activation, row) in output.0.iter_mut().zip(weights.iter()) {
+            *activation = (simd::dot_scalar(&row.0, &black_box(input).0) + *activation).max(0.0);
+        }
+        black_box(output);
+    });
+
+    bench_network::<84, 128>("connect4 84x128x1");
+    bench_network::<768, 256>("chess 768x256x1");
+}
@@ -3,6 +3,10 @@ mod thread;
 
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
+use games::{
+    registry::{Registered, Visitor},
+    GameProtocol,
+};
 use thread::DatagenThread;
 
 #[derive(Default)]
@@ -26,15 +30,36 @@ impl AtomicStats {
     }
 }
 
-pub fn run<G: mentor::Game>() {
-    let threads = parse_args();
+/// Runs the data generator for the game selected with `--game`, after applying the protocol
+/// options given with `--option`, e.g. a variant.
+pub struct Datagen {
+    pub threads: usize,
+    /// Leaves every search evaluates together.
+    pub batch_size: usize,
+    pub options: Vec<(String, String)>,
+}
+
+impl Visitor for Datagen {
+    type Output = ();
+
+    fn visit<G: Registered>(self) {
+        let mut protocol = G::Protocol::default();
+        for (name, value) in &self.options {
+            protocol.set_option(name, value);
+        }
+
+        run::<G>(self.threads, self.batch_size);
+    }
+}
 
+pub fn run<G: Registered>(threads: usize, batch_size: usize) {
     assert!(threads > 0, "Number of threads must be at least 1.");
 
     let params = mentor::mcts::params::SearchParameter::default();
     let settings = mentor::mcts::settings::SearchSettings {
         max_time: Some(1000),
         max_nodes: usize::MAX,
+        batch_size,
     };
 
     let abort_base = AtomicBool::new(false);
@@ -70,25 +95,48 @@ pub fn run<G: mentor::Game>() {
     });
 }
 
-fn parse_args() -> usize {
-    let args: Vec<String> = std::env::args().collect();
+pub fn parse_args(args: &[String]) -> Result<Datagen, String> {
     let mut args = args.iter().skip(1);
 
     let mut threads: Option<usize> = None;
+    let mut batch_size = 1;
+    let mut options = Vec::new();
 
     while let Some(arg) = args.next() {
         match arg.as_str() {
+            "--game" => {
+                args.next();
+            }
             "--threads" => {
                 threads = Some(
                     args.next()
-                        .expect("Argument for number of threads.")
+                        .ok_or("Argument for number of threads.")?
                         .parse()
-                        .expect("Number of threads."),
+                        .map_err(|_| "Number of threads.")?,
                 )
             }
-            _ => panic!("Unknown argument {:?}.", arg),
+            "--batch-size" => {
+                batch_size = args
+                    .next()
+                    .ok_or("Argument for batch size.")?
+                    .parse()
+                    .map_err(|_| "Batch size.")?
+            }
+            "--option" => {
+                let option = args.next().ok_or("Argument for option.")?;
+                let (name, value) = option.split_once('=').ok_or("Option as name=value.")?;
+
+                options.push((name.to_owned(), value.to_owned()));
+            }
+            _ => return Err(format!("Unknown argument {:?}.", arg)),
         }
     }
 
-    threads.expect("Argument for threads.")
+    let threads = threads.ok_or("Argument for threads.")?;
+
+    Ok(Datagen {
+        threads,
+        batch_size,
+        options,
+    })
 }
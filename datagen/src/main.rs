@@ -1,4 +1,15 @@
+use games::registry;
+
 fn main() {
-    #[cfg(feature = "connect4")]
-    datagen::run::<games::connect4::Connect4>();
+    let args: Vec<String> = std::env::args().collect();
+
+    let result = registry::game_arg(&args).and_then(|game| {
+        let datagen = datagen::parse_args(&args)?;
+        registry::visit(game, datagen).map_err(|err| err.to_string())
+    });
+
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
 }
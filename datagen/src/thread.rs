@@ -1,20 +1,21 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use games::registry::Registered;
 use mentor::{
     mcts::{params::SearchParameter, settings::SearchSettings, Search},
-    Game, GameState,
+    GameState,
 };
 
 use crate::{rand::Rand, AtomicStats};
 
-pub struct DatagenThread<G: Game> {
+pub struct DatagenThread<G: Registered> {
     params: SearchParameter,
     settings: SearchSettings,
     positions: Vec<(G, f32)>,
     games: usize,
 }
 
-impl<G: Game> DatagenThread<G> {
+impl<G: Registered> DatagenThread<G> {
     pub fn new(params: SearchParameter, settings: SearchSettings) -> Self {
         DatagenThread {
             params,
@@ -41,44 +42,119 @@ impl<G: Game> DatagenThread<G> {
         }
 
         for (pos, score) in &self.positions {
-            println!("{}\n{}", pos, score);
+            println!("{} | {}", pos.notation(), score);
         }
     }
 
     fn run_game(&mut self) -> usize {
-        let mut pos = G::default();
-
         let mut rand = Rand::default();
-        for _ in 0..(rand.random_range(0, 8)) {
-            let moves = pos.get_legal_moves();
-
-            pos.make_move(moves[rand.random_range(0, moves.len())]);
-        }
+        let plies = rand.random_range(0, 8);
+        let (mut pos, mut history) = Self::opening(&mut rand, plies);
 
         let abort = AtomicBool::new(false);
 
         let mut positions = Vec::with_capacity(42);
 
         while let GameState::Ongoing = pos.game_state() {
-            positions.push(pos);
-            let mut search = Search::new(pos, 50_000);
-
-            let mov = search.run(Some(pos), &self.settings, &self.params, &abort, false);
+            if pos.is_repetition(&history) {
+                break;
+            }
+
+            // Chance positions leave nothing to search, so only decisions are recorded.
+            if pos.is_chance() {
+                history.push(pos.hash());
+                pos.make_move(Self::sample_outcome(&mut rand, &pos.get_outcomes()));
+                continue;
+            }
+
+            positions.push(pos.clone());
+            let mut search = Search::new(pos.clone(), 50_000);
+            search.set_history(history.clone());
+
+            let mov = search.run(
+                Some(pos.clone()),
+                &self.settings,
+                &self.params,
+                &abort,
+                false,
+            );
+            history.push(pos.hash());
             pos.make_move(mov);
         }
 
-        let mut result = match pos.game_state() {
-            GameState::Draw => 0.0,
-            GameState::Loss => 1.0,
-            GameState::Win | GameState::Ongoing => panic!(),
+        // A game which is still ongoing ended by repetition.
+        let rewards = match pos.game_state() {
+            GameState::Ongoing => GameState::Draw.rewards(pos.side_to_move()),
+            state => state.rewards(pos.side_to_move()),
         };
+        let count = positions.len();
+
+        for position in positions.into_iter().rev() {
+            let reward = rewards[position.side_to_move()];
+            self.positions.push((position, reward));
+        }
+
+        count
+    }
+
+    /// Plays up to `plies` random moves from a random start, fewer if the game ends before,
+    /// and returns the position along with the hashes of those before it.
+    fn opening(rand: &mut Rand, plies: usize) -> (G, Vec<u64>) {
+        let mut pos = G::start(rand.random_raw());
+        let mut history = Vec::new();
+
+        for _ in 0..plies {
+            if pos.game_state() != GameState::Ongoing {
+                break;
+            }
+
+            let mov = if pos.is_chance() {
+                Self::sample_outcome(rand, &pos.get_outcomes())
+            } else {
+                let moves = pos.get_legal_moves();
+                moves[rand.random_range(0, moves.len())]
+            };
+
+            history.push(pos.hash());
+            pos.make_move(mov);
+        }
 
-        for i in (0..positions.len()).rev() {
-            self.positions.push((positions[i], result));
+        (pos, history)
+    }
+
+    fn sample_outcome(rand: &mut Rand, outcomes: &[(G::Move, f32)]) -> G::Move {
+        let total: f32 = outcomes.iter().map(|(_, p)| p).sum();
+        let mut x = rand.random() as f32 * total;
+
+        for &(mov, p) in outcomes {
+            if x < p {
+                return mov;
+            }
 
-            result = -result;
+            x -= p;
         }
 
-        positions.len()
+        outcomes[outcomes.len() - 1].0
+    }
+}
+
+#[cfg(all(test, feature = "mnk"))]
+mod thread_test {
+    use super::DatagenThread;
+    use crate::rand::Rand;
+    use games::mnk::TicTacToe;
+    use mentor::{Game, GameState};
+
+    #[test]
+    fn assert_opening_stops_at_game_end() {
+        let mut rand = Rand::default();
+
+        // Tic-tac-toe is over after at most nine plies.
+        for _ in 0..100 {
+            let (pos, history) = DatagenThread::<TicTacToe>::opening(&mut rand, 12);
+
+            assert_ne!(pos.game_state(), GameState::Ongoing);
+            assert!(history.len() <= 9);
+        }
     }
 }
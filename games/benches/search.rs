@@ -0,0 +1,51 @@
+use std::{sync::atomic::AtomicBool, time::Instant};
+
+use games::{
+    connect4::Connect4,
+    go::Go,
+    mnk::{Connect5, Gomoku, TicTacToe},
+};
+use mentor::{
+    mcts::{params::SearchParameter, settings::SearchSettings, Search},
+    Game,
+};
+
+const NODES: usize = 20_000;
+
+/// Searches the start position for a fixed number of nodes, which compares the search on
+/// branching factors from 3x3 tic-tac-toe up to 15x15 Gomoku and 9x9 Go.
+fn bench<G: Game>(name: &str) {
+    let pos = G::default();
+    let settings = SearchSettings {
+        max_time: None,
+        max_nodes: NODES,
+        batch_size: 1,
+    };
+
+    let mut search = Search::new(pos.clone(), NODES * 2);
+    let timer = Instant::now();
+
+    search.run(
+        Some(pos.clone()),
+        &settings,
+        &SearchParameter::default(),
+        &AtomicBool::new(false),
+        false,
+    );
+
+    let elapsed = timer.elapsed();
+    println!(
+        "{:<12} {:>4} moves {:>10.0} nodes/s",
+        name,
+        pos.get_legal_moves().len(),
+        NODES as f64 / elapsed.as_secs_f64()
+    );
+}
+
+fn main() {
+    bench::<TicTacToe>("tictactoe");
+    bench::<Connect4>("connect4");
+    bench::<Connect5>("connect5");
+    bench::<Gomoku>("gomoku");
+    bench::<Go>("go");
+}
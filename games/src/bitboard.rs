@@ -0,0 +1,126 @@
+use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
+
+/// Bitboard of 256 bits for boards that do not fit into a `u64`, e.g. a 15x15 board with a spare
+/// row on top of every column.
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+pub struct WideBitboard(pub [u64; 4]);
+
+impl WideBitboard {
+    pub const BITS: usize = 256;
+    pub const ZERO: WideBitboard = WideBitboard([0; 4]);
+
+    pub fn bit(index: usize) -> Self {
+        let mut bitboard = Self::ZERO;
+        bitboard.0[index / 64] = 1 << (index % 64);
+
+        bitboard
+    }
+
+    pub fn is_set(self, index: usize) -> bool {
+        (self.0[index / 64] >> (index % 64)) & 1 != 0
+    }
+
+    pub fn is_zero(self) -> bool {
+        self == Self::ZERO
+    }
+
+    pub fn count_ones(self) -> u32 {
+        self.0.iter().map(|word| word.count_ones()).sum()
+    }
+}
+
+impl BitAnd for WideBitboard {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        WideBitboard(std::array::from_fn(|i| self.0[i] & rhs.0[i]))
+    }
+}
+
+impl BitOr for WideBitboard {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        WideBitboard(std::array::from_fn(|i| self.0[i] | rhs.0[i]))
+    }
+}
+
+impl Not for WideBitboard {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        WideBitboard(self.0.map(|word| !word))
+    }
+}
+
+impl BitXor for WideBitboard {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        WideBitboard(std::array::from_fn(|i| self.0[i] ^ rhs.0[i]))
+    }
+}
+
+impl Shr<usize> for WideBitboard {
+    type Output = Self;
+
+    /// Shifts towards lower indices, carrying bits across words.
+    fn shr(self, shift: usize) -> Self {
+        let (words, bits) = (shift / 64, shift % 64);
+        let mut result = Self::ZERO;
+
+        for i in 0..4 - words.min(4) {
+            result.0[i] = self.0[i + words] >> bits;
+
+            if bits > 0 && i + words + 1 < 4 {
+                result.0[i] |= self.0[i + words + 1] << (64 - bits);
+            }
+        }
+
+        result
+    }
+}
+
+impl Shl<usize> for WideBitboard {
+    type Output = Self;
+
+    /// Shifts towards higher indices, carrying bits across words.
+    fn shl(self, shift: usize) -> Self {
+        let (words, bits) = (shift / 64, shift % 64);
+        let mut result = Self::ZERO;
+
+        for i in words.min(4)..4 {
+            result.0[i] = self.0[i - words] << bits;
+
+            if bits > 0 && i > words {
+                result.0[i] |= self.0[i - words - 1] >> (64 - bits);
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod bitboard_test {
+    use super::WideBitboard;
+
+    #[test]
+    fn assert_shifts() {
+        for index in [0, 5, 63, 64, 100, 191, 200, 255] {
+            for shift in [1, 15, 16, 64, 70] {
+                let bitboard = WideBitboard::bit(index);
+
+                let expected = index
+                    .checked_sub(shift)
+                    .map_or(WideBitboard::ZERO, WideBitboard::bit);
+                assert!((bitboard >> shift) == expected);
+
+                let expected = Some(index + shift)
+                    .filter(|&i| i < WideBitboard::BITS)
+                    .map_or(WideBitboard::ZERO, WideBitboard::bit);
+                assert!((bitboard << shift) == expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,237 @@
+mod board;
+mod moves;
+
+use std::fmt;
+
+use board::Board;
+use mentor::{
+    mcts::{params::SearchParameter, Search},
+    Game, GameState,
+};
+use moves::Move;
+
+use crate::{fixed_settings, mix, registry::Registered, rollout, GameProtocol};
+
+/// Moves without a capture or a move of a man, 40 for each side, after which the game is drawn.
+const DRAW_PLIES: usize = 80;
+
+/// English checkers. A side without moves loses, and the game is drawn on the third repetition
+/// of a position or after 40 moves of each side without a capture or a move of a man.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct Checkers {
+    board: Board,
+    /// Plies since the last capture or move of a man.
+    reversible: usize,
+}
+
+impl fmt::Display for Checkers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.board.display())
+    }
+}
+
+impl Game for Checkers {
+    type Move = Move;
+
+    const REPETITIONS: usize = 3;
+
+    /// Accepts a FEN as in PDN, e.g. `W:W18,24,K10:B12,16,K22`, or moves from the start position
+    /// separated by whitespace. Moves after an illegal one are ignored.
+    fn from_notation(notation: &str) -> Self {
+        if notation.contains(':') {
+            return Checkers {
+                board: Board::from_fen(notation).unwrap_or_default(),
+                ..Default::default()
+            };
+        }
+
+        let mut pos = Self::default();
+
+        for notation in notation.split_whitespace() {
+            let Some(mov) = pos.parse_move(notation) else {
+                break;
+            };
+
+            pos.make_move(mov);
+        }
+
+        pos
+    }
+
+    fn notation(&self) -> String {
+        self.board.fen()
+    }
+
+    fn side_to_move(&self) -> usize {
+        usize::from(self.board.side_to_move())
+    }
+
+    fn game_state(&self) -> GameState {
+        if self.board.get_moves().is_empty() {
+            return GameState::Loss;
+        }
+
+        if self.reversible >= DRAW_PLIES {
+            GameState::Draw
+        } else {
+            GameState::Ongoing
+        }
+    }
+
+    fn hash(&self) -> u64 {
+        let pieces = u64::from(self.board.pieces(0)) | u64::from(self.board.pieces(1)) << 32;
+        let kings = u64::from(self.board.kings()) << 1 | u64::from(self.board.side_to_move());
+
+        mix(pieces ^ mix(kings))
+    }
+
+    /// Only the positions since the last capture or move of a man can repeat.
+    fn is_repetition(&self, history: &[u64]) -> bool {
+        let hash = self.hash();
+        let reversible = history.len().saturating_sub(self.reversible);
+
+        history[reversible..]
+            .iter()
+            .filter(|&&previous| previous == hash)
+            .count()
+            + 1
+            >= Self::REPETITIONS
+    }
+
+    fn get_value(&mut self) -> f32 {
+        let side_to_move = self.side_to_move();
+        let (pos, _) = rollout(self, usize::MAX);
+
+        match pos.game_state() {
+            GameState::Draw => 0.0,
+            GameState::Loss if side_to_move != pos.side_to_move() => 1.0,
+            _ => -1.0,
+        }
+    }
+
+    fn get_policy(&mut self, moves: &[Self::Move]) -> Vec<f32> {
+        vec![1.0 / moves.len() as f32; moves.len()]
+    }
+
+    fn make_move(&mut self, mov: Self::Move) {
+        if !self.board.is_reversible(mov) {
+            self.reversible = 0;
+        } else if self.reversible < DRAW_PLIES {
+            self.reversible += 1;
+        }
+
+        self.board.make_move(mov);
+    }
+
+    /// Besides the full notation, a capture may be given by its first and last square only,
+    /// e.g. `22x8`, as long as no other capture between them exists.
+    fn parse_move(&self, notation: &str) -> Option<Self::Move> {
+        let moves = self.get_legal_moves();
+
+        if let Some(&mov) = moves.iter().find(|mov| mov.to_string() == notation) {
+            return Some(mov);
+        }
+
+        let (from, to) = notation.split_once('x')?;
+        let (from, to) = (from.parse::<usize>().ok()?, to.parse::<usize>().ok()?);
+        let mut captures = moves
+            .into_iter()
+            .filter(|mov| mov.is_capture() && mov.from() + 1 == from && mov.to() + 1 == to);
+
+        match (captures.next(), captures.next()) {
+            (Some(mov), None) => Some(mov),
+            _ => None,
+        }
+    }
+
+    fn get_legal_moves(&self) -> Vec<Self::Move> {
+        if self.game_state() != GameState::Ongoing {
+            return Vec::new();
+        }
+
+        self.board.get_moves()
+    }
+}
+
+impl Registered for Checkers {
+    type Protocol = CheckersProtocol;
+
+    const NAME: &'static str = "checkers";
+}
+
+#[derive(Default)]
+pub struct CheckersProtocol;
+
+impl GameProtocol for CheckersProtocol {
+    type Game = Checkers;
+
+    const NAME: &'static str = "uci";
+    const NEW_GAME: &'static str = "newgame";
+    const NOTATION: &'static str = "fen";
+
+    const DEFAULT_POS: String = String::new();
+
+    fn options(&mut self) {}
+
+    fn set_option(&mut self, _: &str, _: &str) {}
+
+    fn go(
+        &mut self,
+        pos: &mut Self::Game,
+        search: &mut Search<Self::Game>,
+        params: &SearchParameter,
+        commands: Vec<&str>,
+    ) {
+        self.search(pos, search, params, &fixed_settings(&commands, 5_000));
+    }
+}
+
+#[cfg(test)]
+mod checkers_test {
+    use super::Checkers;
+    use mentor::{Game, GameState};
+
+    #[test]
+    fn assert_captures() {
+        // Black has to capture both white men in one move, after which white has no pieces.
+        let mut pos = Checkers::from_notation("B:W6,15:B1");
+        let moves: Vec<String> = pos
+            .get_legal_moves()
+            .iter()
+            .map(|m| m.to_string())
+            .collect();
+        assert_eq!(moves, ["1x10x19"]);
+
+        pos.make_move(pos.parse_move("1x19").unwrap());
+        assert_eq!(pos.notation(), "W:W:B19");
+        assert_eq!(pos.game_state(), GameState::Loss);
+
+        // A man crowned by a capture ends its move, even though a king could jump on over 27.
+        let mut pos = Checkers::from_notation("B:W26,27:B22");
+        let moves: Vec<String> = pos
+            .get_legal_moves()
+            .iter()
+            .map(|m| m.to_string())
+            .collect();
+        assert_eq!(moves, ["22x31"]);
+
+        pos.make_move(pos.get_legal_moves()[0]);
+        assert_eq!(pos.notation(), "W:W27:BK31");
+    }
+
+    #[test]
+    fn assert_draws() {
+        // Kings moving back and forth repeat the position for the third time after eight moves.
+        let mut pos = Checkers::from_notation("B:WK32:BK1");
+        let mut history = Vec::new();
+        for notation in ["1-5", "32-28", "5-1", "28-32"].repeat(2) {
+            assert!(!pos.is_repetition(&history));
+
+            history.push(pos.hash());
+            pos.make_move(pos.parse_move(notation).unwrap());
+        }
+
+        assert!(pos.is_repetition(&history));
+        assert_eq!(pos.game_state(), GameState::Ongoing);
+    }
+}
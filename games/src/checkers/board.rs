@@ -0,0 +1,332 @@
+use super::moves::Move;
+
+/// Marks a step or jump which leaves the board.
+const NONE: u8 = 32;
+
+/// Neighbour of every square in the directions up-left, up-right, down-left and down-right,
+/// where up is towards square 1. Squares are indexed from 0, four per row.
+pub static STEPS: [[u8; 4]; 32] = neighbours(1);
+
+/// Landing square of a jump in each direction.
+pub static JUMPS: [[u8; 4]; 32] = neighbours(2);
+
+const fn neighbours(distance: i32) -> [[u8; 4]; 32] {
+    let mut table = [[NONE; 4]; 32];
+
+    let mut square = 0;
+    while square < 32 {
+        let row = (square / 4) as i32;
+        let col = (2 * (square % 4) + (1 - row as usize % 2)) as i32;
+
+        let mut direction = 0;
+        while direction < 4 {
+            // Directions 0 and 1 go up, and directions 0 and 2 go left.
+            let r = row + distance * (2 * (direction as i32 / 2) - 1);
+            let c = col + distance * (2 * (direction as i32 % 2) - 1);
+
+            if r >= 0 && r < 8 && c >= 0 && c < 8 {
+                table[square][direction] = (r * 4 + c / 2) as u8;
+            }
+
+            direction += 1;
+        }
+
+        square += 1;
+    }
+
+    table
+}
+
+const KING_ROWS: [u32; 2] = [0xF000_0000, 0x0000_000F];
+
+/// English checkers board. Black starts on squares 1 to 12, moves towards square 32 and moves
+/// first.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Board {
+    pieces: [u32; 2],
+    kings: u32,
+    side_to_move: bool,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board {
+            pieces: [0x0000_0FFF, 0xFFF0_0000],
+            kings: 0,
+            side_to_move: false,
+        }
+    }
+}
+
+impl Board {
+    /// Parses a FEN as in PDN, e.g. `B:W18,24,K10:B12,16,K22`, with the side to move followed by
+    /// the squares of each colour, `K` marking kings. Ranges like `1-12` are accepted as well.
+    pub fn from_fen(fen: &str) -> Option<Self> {
+        let fen = fen.trim_matches(|c| c == '"' || c == '.' || char::is_whitespace(c));
+        let mut parts = fen.split(':');
+
+        let side_to_move = match parts.next()? {
+            "B" => false,
+            "W" => true,
+            _ => return None,
+        };
+
+        let mut board = Board {
+            pieces: [0; 2],
+            kings: 0,
+            side_to_move,
+        };
+
+        for part in parts {
+            let side = match part.chars().next()? {
+                'B' => 0,
+                'W' => 1,
+                _ => return None,
+            };
+
+            for token in part[1..].split(',').filter(|token| !token.is_empty()) {
+                let (king, token) = match token.strip_prefix('K') {
+                    Some(token) => (true, token),
+                    None => (false, token),
+                };
+
+                let (first, last) = token.split_once('-').unwrap_or((token, token));
+                let (first, last) = (first.parse::<usize>().ok()?, last.parse::<usize>().ok()?);
+                if first == 0 || last > 32 {
+                    return None;
+                }
+
+                for square in first - 1..last {
+                    board.pieces[side] |= 1 << square;
+                    if king {
+                        board.kings |= 1 << square;
+                    }
+                }
+            }
+        }
+
+        Some(board)
+    }
+
+    pub fn fen(&self) -> String {
+        let side = if self.side_to_move { 'W' } else { 'B' };
+        let squares = |side: usize| {
+            (0..32)
+                .filter(|&square| self.pieces[side] & 1 << square != 0)
+                .map(|square| {
+                    let king = if self.kings & 1 << square != 0 {
+                        "K"
+                    } else {
+                        ""
+                    };
+                    format!("{}{}", king, square + 1)
+                })
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        format!("{}:W{}:B{}", side, squares(1), squares(0))
+    }
+
+    pub fn side_to_move(&self) -> bool {
+        self.side_to_move
+    }
+
+    pub fn pieces(&self, side: usize) -> u32 {
+        self.pieces[side]
+    }
+
+    pub fn kings(&self) -> u32 {
+        self.kings
+    }
+
+    fn directions(&self, square: usize) -> &'static [usize] {
+        if self.kings & 1 << square != 0 {
+            &[0, 1, 2, 3]
+        } else if self.side_to_move {
+            &[0, 1]
+        } else {
+            &[2, 3]
+        }
+    }
+
+    /// Captures are mandatory and every capture has to be followed up with further jumps until
+    /// none are left. A man which is crowned during a capture ends its move.
+    pub fn get_moves(&self) -> Vec<Move> {
+        let side = usize::from(self.side_to_move);
+        let own = self.pieces[side];
+        let occupied = self.pieces[0] | self.pieces[1];
+        let mut moves = Vec::new();
+
+        for from in (0..32).filter(|&square| own & 1 << square != 0) {
+            // The moving piece no longer blocks its own square, which a king may jump back to.
+            self.jumps(
+                from,
+                from,
+                0,
+                occupied & !(1 << from),
+                Move::capture(from),
+                &mut moves,
+            );
+        }
+
+        if !moves.is_empty() {
+            return moves;
+        }
+
+        for from in (0..32).filter(|&square| own & 1 << square != 0) {
+            for &direction in self.directions(from) {
+                let to = STEPS[from][direction];
+
+                if to != NONE && occupied & 1 << to == 0 {
+                    moves.push(Move::step(from, direction));
+                }
+            }
+        }
+
+        moves
+    }
+
+    fn jumps(
+        &self,
+        from: usize,
+        square: usize,
+        captured: u32,
+        occupied: u32,
+        mov: Move,
+        moves: &mut Vec<Move>,
+    ) {
+        let opponent = self.pieces[usize::from(!self.side_to_move)];
+        let crowned = self.kings & 1 << from == 0
+            && KING_ROWS[usize::from(self.side_to_move)] & 1 << square != 0;
+
+        let mut extended = false;
+        if !crowned {
+            for &direction in self.directions(from) {
+                let (over, to) = (STEPS[square][direction], JUMPS[square][direction]);
+                if to == NONE {
+                    continue;
+                }
+
+                let over_bit = 1 << over;
+                if opponent & over_bit != 0 && captured & over_bit == 0 && occupied & 1 << to == 0 {
+                    let mov = mov.then(direction);
+                    self.jumps(
+                        from,
+                        usize::from(to),
+                        captured | over_bit,
+                        occupied,
+                        mov,
+                        moves,
+                    );
+                    extended = true;
+                }
+            }
+        }
+
+        if !extended && captured != 0 {
+            moves.push(mov);
+        }
+    }
+
+    /// Whether the move can be undone by later moves, which holds for moves of kings without a
+    /// capture.
+    pub fn is_reversible(&self, mov: Move) -> bool {
+        !mov.is_capture() && self.kings & 1 << mov.from() != 0
+    }
+
+    pub fn make_move(&mut self, mov: Move) {
+        let side = usize::from(self.side_to_move);
+        let from = mov.from();
+
+        let mut to = from;
+        for direction in mov.directions() {
+            if mov.is_capture() {
+                let over = 1 << STEPS[to][direction];
+
+                self.pieces[side ^ 1] &= !over;
+                self.kings &= !over;
+                to = usize::from(JUMPS[to][direction]);
+            } else {
+                to = usize::from(STEPS[to][direction]);
+            }
+        }
+
+        let (from_bit, to_bit) = (1 << from, 1 << to);
+        self.pieces[side] = self.pieces[side] & !from_bit | to_bit;
+
+        if self.kings & from_bit != 0 || KING_ROWS[side] & to_bit != 0 {
+            self.kings = self.kings & !from_bit | to_bit;
+        }
+
+        self.side_to_move ^= true;
+    }
+
+    pub fn display(&self) -> String {
+        let separator = format!("+{}\n", "---+".repeat(8));
+        let mut board = String::new();
+
+        for row in 0..8 {
+            board.push_str(&separator);
+
+            for col in 0..8 {
+                let cell = if (row + col) % 2 == 0 {
+                    ' '
+                } else {
+                    let square = row * 4 + col / 2;
+
+                    match (
+                        self.pieces[0] & 1 << square != 0,
+                        self.pieces[1] & 1 << square != 0,
+                    ) {
+                        (true, _) if self.kings & 1 << square != 0 => 'B',
+                        (true, _) => 'b',
+                        (_, true) if self.kings & 1 << square != 0 => 'W',
+                        (_, true) => 'w',
+                        _ => '.',
+                    }
+                };
+
+                board.push_str(&format!("| {} ", cell));
+            }
+
+            board.push_str(&format!("| {}-{}\n", row * 4 + 1, row * 4 + 4));
+        }
+
+        board.push_str(&separator);
+        board
+    }
+}
+
+#[cfg(test)]
+mod board_test {
+    use super::Board;
+
+    fn perft(board: &Board, depth: usize) -> usize {
+        let moves = board.get_moves();
+        if depth == 1 {
+            return moves.len();
+        }
+
+        moves
+            .into_iter()
+            .map(|mov| {
+                let mut board = *board;
+                board.make_move(mov);
+                perft(&board, depth - 1)
+            })
+            .sum()
+    }
+
+    #[test]
+    fn assert_perft() {
+        let board = Board::default();
+
+        for (depth, nodes) in [7, 49, 302, 1469, 7361, 36768, 179740]
+            .into_iter()
+            .enumerate()
+        {
+            assert_eq!(perft(&board, depth + 1), nodes);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use super::board::{JUMPS, STEPS};
+
+/// Move of the piece on `from`, followed by the direction of a single step or of every jump of
+/// a capture. The directions are stored behind a leading one bit, which marks their number and
+/// leaves room for the twelve jumps needed to capture every piece.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Move(pub u32);
+
+impl Move {
+    const CAPTURE: u32 = 1 << 5;
+
+    pub fn step(from: usize, direction: usize) -> Self {
+        Move(from as u32 | (0b100 | direction as u32) << 6)
+    }
+
+    /// Capture without any jumps yet, to be extended with `then`.
+    pub fn capture(from: usize) -> Self {
+        Move(from as u32 | Self::CAPTURE | 1 << 6)
+    }
+
+    pub fn then(self, direction: usize) -> Self {
+        let path = (self.0 >> 6) << 2 | direction as u32;
+
+        Move(self.0 & 0b11_1111 | path << 6)
+    }
+
+    pub fn from(self) -> usize {
+        (self.0 & 0b1_1111) as usize
+    }
+
+    pub fn is_capture(self) -> bool {
+        self.0 & Self::CAPTURE != 0
+    }
+
+    pub fn directions(self) -> impl Iterator<Item = usize> {
+        let path = self.0 >> 6;
+        let count = (31 - path.leading_zeros()) / 2;
+
+        (0..count)
+            .rev()
+            .map(move |i| ((path >> (2 * i)) & 0b11) as usize)
+    }
+
+    /// Squares the piece lands on, ending with its destination.
+    pub fn squares(self) -> Vec<usize> {
+        let table = if self.is_capture() { &JUMPS } else { &STEPS };
+
+        self.directions()
+            .scan(self.from(), |square, direction| {
+                *square = usize::from(table[*square][direction]);
+                Some(*square)
+            })
+            .collect()
+    }
+
+    pub fn to(self) -> usize {
+        self.squares().last().copied().unwrap_or(self.from())
+    }
+}
+
+/// Squares are numbered from 1 to 32 as in PDN, e.g. `11-15` or `22x15x8` for a capture with
+/// every square landed on.
+impl std::fmt::Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let separator = if self.is_capture() { "x" } else { "-" };
+
+        write!(f, "{}", self.from() + 1)?;
+        for square in self.squares() {
+            write!(f, "{}{}", separator, square + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<u32> for Move {
+    fn from(mov: u32) -> Self {
+        Move(mov)
+    }
+}
+
+impl From<Move> for u32 {
+    fn from(mov: Move) -> Self {
+        mov.0
+    }
+}
@@ -1,56 +1,622 @@
 mod board;
+mod eval;
+mod features;
 mod moves;
+mod network;
+mod notation;
+mod pgn;
 mod types;
 mod util;
+mod variant;
+mod zobrist;
+
+pub use self::{
+    pgn::Pgn,
+    variant::{set_variant, Variant},
+};
 
 use core::fmt;
+use std::{io, path::Path};
+
+use self::{
+    board::{Board, Changes, STARTPOS},
+    features::Accumulators,
+    moves::Move,
+    types::{bitboard::Bitboard, square::Square},
+    util::Piece,
+    variant::variant,
+};
+use mentor::{
+    mcts::{params::SearchParameter, settings::SearchSettings, Search},
+    network::activation::softmax,
+    Game, GameState, UnmakeMove,
+};
+
+use crate::{registry::Registered, rollout, tune::Tunable, GameProtocol};
 
-use self::{board::Board, moves::Move};
-use mentor::Game;
+/// Plies after which an Antichess rollout counts as a draw.
+const ROLLOUT_PLIES: usize = 200;
 
-#[derive(Copy, Clone, Default, PartialEq, Eq)]
+#[derive(Clone)]
 pub struct Chess {
     board: Board,
+    accumulators: Option<Accumulators>,
+    /// Board before every move and the pieces the move changed, which take it back. Only kept
+    /// while recording for a search with `UnmakeMove`.
+    undo: Option<Vec<(Board, Changes)>>,
 }
 
+impl Chess {
+    /// Loads the value network. Its first layer is then updated incrementally by `make_move`
+    /// in every position created afterwards.
+    pub fn load_value_network(path: &Path) -> io::Result<()> {
+        network::load_value(path)
+    }
+
+    pub fn load_policy_network(path: &Path) -> io::Result<()> {
+        network::load_policy(path)
+    }
+
+    /// Loads tuned weights for the handcrafted evaluation.
+    pub fn load_eval_parameters(path: &Path) -> io::Result<()> {
+        eval::load(path)
+    }
+
+    /// The networks are trained on standard chess, so the other variants do without them.
+    fn from_board(board: Board) -> Self {
+        Chess {
+            board,
+            accumulators: network::value()
+                .filter(|_| board.variant() == Variant::Standard)
+                .map(|network| Accumulators::refresh(network.transformer(), &board)),
+            undo: None,
+        }
+    }
+
+    pub fn variant(&self) -> Variant {
+        self.board.variant()
+    }
+
+    /// Handcrafted evaluation squashed into [-1, 1] for the side to move.
+    fn evaluate(&self) -> f32 {
+        let parameters = eval::parameters();
+
+        let eval = parameters.evaluate(&eval::trace(&self.board));
+        let eval = if self.board.side_to_move { -eval } else { eval };
+
+        parameters.squash(eval)
+    }
+
+    /// Prior favouring captures that win material by static exchange evaluation, promotions and
+    /// moves to better squares.
+    fn heuristic_policy(&self, moves: &[Move]) -> Vec<f32> {
+        let side = usize::from(self.board.side_to_move);
+        let parameters = eval::parameters();
+
+        let logits: Vec<f32> = moves
+            .iter()
+            .map(|&mov| {
+                let piece = match mov.dropped() {
+                    Some(piece) => piece,
+                    None => {
+                        self.board
+                            .piece_at(mov.from)
+                            .expect("Piece on from square.")
+                            .1
+                    }
+                };
+
+                let pst = |square: Square| {
+                    let square = square as usize ^ (56 * side);
+                    parameters.weights[eval::PST + (piece - Piece::PAWN) * 64 + square].0
+                };
+
+                let mut logit = self.board.see(mov) as f32 / 200.0;
+                logit += (pst(mov.to) - pst(mov.from)) / 100.0;
+
+                if let Some(promotion) = mov.promotion() {
+                    logit += if promotion == Piece::QUEEN { 3.0 } else { -2.0 };
+                }
+
+                logit
+            })
+            .collect();
+
+        softmax(&logits)
+    }
+
+    /// Plays random moves without updating the accumulators.
+    fn rollout(&self) -> f32 {
+        let pos = Chess {
+            board: self.board,
+            accumulators: None,
+            undo: None,
+        };
+        let (pos, _) = rollout(&pos, ROLLOUT_PLIES);
+
+        match pos.game_state() {
+            GameState::Ongoing | GameState::Draw => 0.0,
+            state if (state == GameState::Win) == (pos.side_to_move() == self.side_to_move()) => {
+                1.0
+            }
+            _ => -1.0,
+        }
+    }
+
+    /// State of the game given the legal moves, so that they are generated once when both are
+    /// needed. Crazyhouse has no fifty-move rule and only standard chess draws on insufficient
+    /// material.
+    fn state(&self, moves: &[Move]) -> GameState {
+        let variant = self.board.variant();
+        let side = usize::from(self.board.side_to_move);
+
+        if variant == Variant::Atomic && self.board.bitboard(side, Piece::KING) == Bitboard::ZERO {
+            return GameState::Loss;
+        }
+
+        if moves.is_empty() {
+            return match variant {
+                Variant::Antichess => GameState::Win,
+                _ if self.board.is_check() => GameState::Loss,
+                _ => GameState::Draw,
+            };
+        }
+
+        if variant != Variant::Crazyhouse && self.board.half_moves() >= 100
+            || variant == Variant::Standard && self.insufficient_material()
+        {
+            return GameState::Draw;
+        }
+
+        GameState::Ongoing
+    }
+
+    fn insufficient_material(&self) -> bool {
+        let pieces = [Piece::PAWN, Piece::ROOK, Piece::QUEEN]
+            .iter()
+            .fold(Bitboard::ZERO, |acc, &piece| {
+                acc | self.board.bitboard(0, piece) | self.board.bitboard(1, piece)
+            });
+
+        pieces == Bitboard::ZERO && self.board.occupancy().0.count_ones() <= 3
+    }
+}
+
+impl Default for Chess {
+    fn default() -> Self {
+        Chess::from_board(Board::from_variant_fen(STARTPOS, variant()))
+    }
+}
+
+impl PartialEq for Chess {
+    fn eq(&self, other: &Self) -> bool {
+        self.board == other.board
+    }
+}
+
+impl Eq for Chess {}
+
 impl Game for Chess {
     type Move = Move;
 
-    fn from_notation(_: &str) -> Self {
-        todo!()
+    const REPETITIONS: usize = 3;
+
+    /// Accepts `startpos` or a FEN of the variant set by `set_variant`.
+    fn from_notation(notation: &str) -> Self {
+        match notation {
+            "startpos" => Chess::default(),
+            fen => Chess::from_board(Board::from_variant_fen(fen, variant())),
+        }
+    }
+
+    fn notation(&self) -> String {
+        self.board.fen()
     }
 
     fn side_to_move(&self) -> usize {
         usize::from(self.board.side_to_move)
     }
 
-    fn game_state(&self) -> mentor::GameState {
-        todo!()
+    fn game_state(&self) -> GameState {
+        self.state(&self.board.gen_legal_moves())
     }
 
     fn hash(&self) -> u64 {
-        todo!()
+        self.board.hash()
     }
 
+    /// Only the positions since the last capture or pawn move can repeat.
+    fn is_repetition(&self, history: &[u64]) -> bool {
+        let hash = self.hash();
+        let reversible = history
+            .len()
+            .saturating_sub(usize::from(self.board.half_moves()));
+
+        history[reversible..]
+            .iter()
+            .filter(|&&previous| previous == hash)
+            .count()
+            + 1
+            >= Self::REPETITIONS
+    }
+
+    /// The handcrafted evaluation is a poor guide to Antichess, where losing material is the aim,
+    /// so it is played out with random moves instead.
     fn get_value(&mut self) -> f32 {
-        todo!()
+        if self.board.variant() == Variant::Antichess {
+            return self.rollout();
+        }
+
+        let Some(network) = network::value().filter(|_| self.accumulators.is_some()) else {
+            return self.evaluate();
+        };
+
+        let accumulators = *self
+            .accumulators
+            .get_or_insert_with(|| Accumulators::refresh(network.transformer(), &self.board));
+
+        network.value(&accumulators, self.side_to_move())
     }
 
-    fn get_policy(&mut self, _: &[Self::Move]) -> Vec<f32> {
-        todo!()
+    /// Positions with accumulators, which only standard chess has, share one pass through the
+    /// output layer of the network.
+    fn get_values(positions: &mut [Self]) -> Vec<f32> {
+        let Some(network) = network::value() else {
+            return positions.iter_mut().map(|pos| pos.get_value()).collect();
+        };
+
+        let batch: Vec<_> = positions
+            .iter()
+            .filter_map(|pos| Some((pos.accumulators.as_ref()?, pos.side_to_move())))
+            .collect();
+        let mut batch = network.values(&batch).into_iter();
+
+        positions
+            .iter_mut()
+            .map(|pos| match pos.accumulators {
+                Some(_) => batch.next().unwrap(),
+                None => pos.get_value(),
+            })
+            .collect()
     }
 
-    fn make_move(&mut self, _: Self::Move) {
-        todo!()
+    fn get_policy(&mut self, moves: &[Self::Move]) -> Vec<f32> {
+        match network::policy() {
+            Some(network) if self.board.variant() == Variant::Standard => {
+                network.policy(&self.board, moves)
+            }
+            _ => self.heuristic_policy(moves),
+        }
+    }
+
+    fn make_move(&mut self, mov: Self::Move) {
+        let board = self.board;
+        let (_, changes) = self.board.make_move(mov);
+
+        if let (Some(accumulators), Some(network)) = (self.accumulators.as_mut(), network::value())
+        {
+            accumulators.update(network.transformer(), &changes);
+        }
+
+        if let Some(undo) = self.undo.as_mut() {
+            undo.push((board, changes));
+        }
+    }
+
+    /// Accepts long algebraic notation as used by UCI, e.g. `e7e8q`, and standard algebraic
+    /// notation, e.g. `e8=Q+`.
+    fn parse_move(&self, notation: &str) -> Option<Self::Move> {
+        notation::parse_uci(&self.board, notation)
+            .or_else(|| notation::parse_san(&self.board, notation))
     }
 
     fn get_legal_moves(&self) -> Vec<Self::Move> {
-        self.board.gen_moves()
+        let moves = self.board.gen_legal_moves();
+        if self.state(&moves) != GameState::Ongoing {
+            return Vec::new();
+        }
+
+        moves
+    }
+}
+
+impl UnmakeMove for Chess {
+    fn record_moves(&mut self, record: bool) {
+        self.undo = record.then(Vec::new);
+    }
+
+    fn unmake_move(&mut self, _mov: Self::Move) {
+        let (board, changes) = self
+            .undo
+            .as_mut()
+            .and_then(Vec::pop)
+            .expect("Move to take back.");
+        self.board = board;
+
+        if let (Some(accumulators), Some(network)) = (self.accumulators.as_mut(), network::value())
+        {
+            accumulators.revert(network.transformer(), &changes);
+        }
+    }
+}
+
+impl Tunable for Chess {
+    fn parameters() -> Vec<f32> {
+        eval::parameters()
+            .weights
+            .iter()
+            .flat_map(|weight| [weight.0, weight.1])
+            .collect()
+    }
+
+    fn scale() -> f32 {
+        eval::parameters().scale
+    }
+
+    fn coefficients(&self) -> Vec<(usize, f32)> {
+        let trace = eval::trace(&self.board);
+
+        let sign = if self.board.side_to_move { -1.0 } else { 1.0 };
+        let phase = trace.phase as f32 / eval::MAX_PHASE as f32;
+
+        trace
+            .coefficients
+            .iter()
+            .enumerate()
+            .filter(|(_, &coefficient)| coefficient != 0)
+            .flat_map(|(i, &coefficient)| {
+                let coefficient = sign * f32::from(coefficient);
+                [
+                    (2 * i, coefficient * phase),
+                    (2 * i + 1, coefficient * (1.0 - phase)),
+                ]
+            })
+            .collect()
+    }
+
+    fn group() -> usize {
+        2
     }
 }
 
 impl fmt::Display for Chess {
-    fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        todo!()
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\nFen: {}", self.board.display(), self.board.fen())
+    }
+}
+
+impl Registered for Chess {
+    type Protocol = ChessProtocol;
+
+    const NAME: &'static str = "chess";
+}
+
+#[derive(Default)]
+pub struct ChessProtocol;
+
+impl ChessProtocol {
+    /// Search limits of a `go` command. Without a fixed move time a share of the remaining clock
+    /// is used, and `infinite` searches until `stop`.
+    fn settings(side: usize, commands: &[&str]) -> SearchSettings {
+        let value = |name: &str| {
+            commands
+                .iter()
+                .position(|&c| c == name)
+                .and_then(|i| commands.get(i + 1))
+                .and_then(|v| v.parse::<u128>().ok())
+        };
+
+        let (time, increment) = if side == 0 {
+            (value("wtime"), value("winc"))
+        } else {
+            (value("btime"), value("binc"))
+        };
+
+        let max_time = match (value("movetime"), time) {
+            _ if commands.contains(&"infinite") => None,
+            (Some(movetime), _) => Some(movetime),
+            (None, Some(time)) => {
+                let moves_to_go = value("movestogo").unwrap_or(30).max(1);
+                let budget = time / moves_to_go + increment.unwrap_or(0) * 3 / 4;
+
+                Some(budget.min(time.saturating_sub(50)).max(1))
+            }
+            (None, None) if value("nodes").is_some() => None,
+            (None, None) => Some(10_000),
+        };
+
+        SearchSettings {
+            max_time,
+            max_nodes: value("nodes").map_or(usize::MAX, |nodes| nodes as usize),
+            batch_size: crate::batch_size(),
+        }
+    }
+}
+
+impl GameProtocol for ChessProtocol {
+    type Game = Chess;
+
+    const NAME: &'static str = "uci";
+    const NEW_GAME: &'static str = "ucinewgame";
+    const NOTATION: &'static str = "fen";
+
+    const DEFAULT_POS: String = String::new();
+
+    fn new_search(pos: &Chess) -> Search<Chess> {
+        Search::with_unmake(pos.clone(), 50_000)
+    }
+
+    fn options(&mut self) {
+        println!("option name ValueNet type string default <empty>");
+        println!("option name PolicyNet type string default <empty>");
+        println!("option name EvalFile type string default <empty>");
+        println!("option name UCI_Chess960 type check default false");
+        println!(
+            "option name UCI_Variant type combo default chess var chess var crazyhouse var atomic \
+             var antichess"
+        );
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) {
+        if name == "UCI_Chess960" {
+            moves::set_chess960(value == "true");
+            return;
+        }
+
+        if name == "UCI_Variant" {
+            match Variant::from_name(value) {
+                Some(variant) => set_variant(variant),
+                None => println!("info string unknown variant {}", value),
+            }
+            return;
+        }
+
+        if value.is_empty() || value == "<empty>" {
+            return;
+        }
+
+        let path = Path::new(value);
+        let result = match name {
+            "ValueNet" => Chess::load_value_network(path),
+            "PolicyNet" => Chess::load_policy_network(path),
+            "EvalFile" => Chess::load_eval_parameters(path),
+            _ => return,
+        };
+
+        if let Err(err) = result {
+            println!("info string failed to load {}: {}", name, err);
+        }
+    }
+
+    fn go(
+        &mut self,
+        pos: &mut Self::Game,
+        search: &mut Search<Self::Game>,
+        params: &SearchParameter,
+        commands: Vec<&str>,
+    ) {
+        let settings = Self::settings(pos.side_to_move(), &commands);
+        self.search(pos, search, params, &settings);
+    }
+}
+
+#[cfg(test)]
+mod chess_test {
+    use super::{board::Board, Chess, ChessProtocol, Variant};
+    use crate::GameProtocol;
+    use mentor::{Game, GameState, UnmakeMove};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn play(variant: Variant, fen: &str, moves: &[&str]) -> Chess {
+        let mut pos = Chess::from_board(Board::from_variant_fen(fen, variant));
+        for notation in moves {
+            pos.make_move(pos.parse_move(notation).unwrap());
+        }
+
+        pos
+    }
+
+    #[test]
+    fn assert_variants() {
+        // The captured pawn goes to the pocket and is dropped back.
+        let pos = play(
+            Variant::Crazyhouse,
+            "4k3/8/8/8/8/8/3p4/4K3[] w - - 0 1",
+            &["Kxd2", "Ke7", "P@e4"],
+        );
+        assert_eq!(pos.notation(), "8/4k3/8/8/4P3/8/3K4/8[] b - - 0 2");
+
+        // Capturing the pawn next to the king explodes it.
+        let pos = play(
+            Variant::Atomic,
+            "4k3/5p2/8/7Q/8/8/8/4K3 w - - 0 1",
+            &["Qxf7"],
+        );
+        assert_eq!(pos.notation(), "8/8/8/8/8/8/8/4K3 b - - 0 1");
+        assert_eq!(pos.game_state(), GameState::Loss);
+
+        // White has to give away the bishop, after which black has no pieces left and wins.
+        let pos = play(Variant::Antichess, "8/8/8/8/8/8/1p5P/B7 w - - 0 1", &[]);
+        assert_eq!(pos.get_legal_moves().len(), 1);
+
+        let pos = play(
+            Variant::Antichess,
+            "8/8/8/8/8/8/1p6/B7 w - - 0 1",
+            &["Bxb2"],
+        );
+        assert_eq!(pos.game_state(), GameState::Win);
+    }
+
+    #[test]
+    fn assert_position_command() {
+        const FEN: &str = "4k3/8/8/8/8/8/8/4K2R w K - 0 1";
+        let mut protocol = ChessProtocol;
+        let mut pos = Chess::default();
+
+        // The FEN may end the line, in which case all of its fields belong to it.
+        let command = format!("position fen {}", FEN);
+        let history = protocol
+            .position(&mut pos, command.split_whitespace().collect())
+            .unwrap();
+
+        assert!(history.is_empty());
+        assert_eq!(pos.notation(), FEN);
+
+        let command = format!("position fen {} moves e1g1 e8d7", FEN);
+        let history = protocol
+            .position(&mut pos, command.split_whitespace().collect())
+            .unwrap();
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(pos.notation(), "8/3k4/8/8/8/8/8/5RK1 w - - 2 2");
+    }
+
+    #[test]
+    fn assert_unmake() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for variant in [Variant::Standard, Variant::Crazyhouse, Variant::Atomic] {
+            // Moves of a game are not kept for taking them back.
+            let start = play(variant, super::STARTPOS, &["e4", "e5"]);
+            assert!(start.undo.is_none());
+
+            let mut pos = start.clone();
+            let mut moves = Vec::new();
+            pos.record_moves(true);
+
+            while moves.len() < 60 && pos.game_state() == GameState::Ongoing {
+                let legal = pos.get_legal_moves();
+                let mov = legal[rng.gen_range(0..legal.len())];
+
+                pos.make_move(mov);
+                moves.push(mov);
+            }
+
+            for &mov in moves.iter().rev() {
+                pos.unmake_move(mov);
+            }
+
+            assert_eq!(pos.notation(), start.notation());
+            assert_eq!(pos.hash(), start.hash());
+
+            pos.record_moves(false);
+            assert!(pos.undo.is_none());
+        }
+    }
+
+    #[test]
+    fn assert_repetition() {
+        let mut pos = Chess::default();
+        let mut history = Vec::new();
+
+        for notation in ["Nf3", "Nf6", "Ng1", "Ng8"].repeat(2) {
+            assert!(!pos.is_repetition(&history));
+
+            history.push(pos.hash());
+            pos.make_move(pos.parse_move(notation).unwrap());
+        }
+
+        // The start position occurs for the third time.
+        assert!(pos.is_repetition(&history));
     }
 }
@@ -1,4 +1,4 @@
-use super::{moves::Move, types::bitboard::Bitboard};
+use super::{moves::Move, types::bitboard::Bitboard, variant::Variant, zobrist};
 use crate::{
     bitboard_loop,
     chess::{
@@ -6,23 +6,549 @@ use crate::{
             get_bishop_moves, get_king_moves, get_knight_moves, get_pawn_attacks, get_queen_moves,
             get_rook_moves,
         },
-        types::{rank::Rank, square::Square},
+        types::{file::File, rank::Rank, square::Square},
         util::{Castle, Flag, Piece},
     },
 };
 
+pub const STARTPOS: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+const PIECE_CHARS: [[char; 6]; 2] = [
+    ['P', 'N', 'B', 'R', 'Q', 'K'],
+    ['p', 'n', 'b', 'r', 'q', 'k'],
+];
+
+/// Promotion pieces of every variant but Antichess, which adds the king.
+const PROMOTIONS: [usize; 5] = [
+    Piece::KNIGHT,
+    Piece::BISHOP,
+    Piece::ROOK,
+    Piece::QUEEN,
+    Piece::KING,
+];
+
+/// Pieces put on or taken off the board by a single move, as `(side, piece, square)`. An atomic
+/// capture removes up to ten pieces.
+#[derive(Clone, Copy)]
+pub struct Changes {
+    added: [(usize, usize, Square); 2],
+    removed: [(usize, usize, Square); 10],
+    added_len: usize,
+    removed_len: usize,
+}
+
+impl Default for Changes {
+    fn default() -> Self {
+        Changes {
+            added: [(0, 0, Square::A1); 2],
+            removed: [(0, 0, Square::A1); 10],
+            added_len: 0,
+            removed_len: 0,
+        }
+    }
+}
+
+impl Changes {
+    pub fn added(&self) -> &[(usize, usize, Square)] {
+        &self.added[..self.added_len]
+    }
+
+    pub fn removed(&self) -> &[(usize, usize, Square)] {
+        &self.removed[..self.removed_len]
+    }
+
+    fn add(&mut self, side: usize, piece: usize, square: Square) {
+        self.added[self.added_len] = (side, piece, square);
+        self.added_len += 1;
+    }
+
+    fn remove(&mut self, side: usize, piece: usize, square: Square) {
+        self.removed[self.removed_len] = (side, piece, square);
+        self.removed_len += 1;
+    }
+}
+
 #[derive(Copy, Clone, Default, PartialEq, Eq)]
 pub struct Board {
     pub side_to_move: bool,
     bitboards: [Bitboard; 8],
     half_moves: u8,
+    full_moves: u16,
     castle_rights: u8,
+    /// Start files of the castling rooks of each side, kingside first. Differs from the h and a
+    /// files only in Chess960.
+    rook_files: [[u8; 2]; 2],
     en_passant_rank: u8,
     check: bool,
     hash: u64,
+    variant: Variant,
+    /// Pieces in the pocket of each side in Crazyhouse, from pawns to queens.
+    pockets: [[u8; 5]; 2],
+    /// Pieces promoted from pawns, which are only tracked in Crazyhouse.
+    promoted: Bitboard,
 }
 
 impl Board {
+    #[cfg(test)]
+    pub fn from_fen(fen: &str) -> Board {
+        Board::from_variant_fen(fen, Variant::Standard)
+    }
+
+    /// Parses a FEN of the given variant. Crazyhouse adds the pockets in brackets after the
+    /// placement, e.g. `.../RNBQKBNR[Qp]`, and marks promoted pieces with a `~` after them.
+    pub fn from_variant_fen(fen: &str, variant: Variant) -> Board {
+        let mut board = Board {
+            rook_files: [[7, 0]; 2],
+            variant,
+            ..Default::default()
+        };
+        let mut parts = fen.split_whitespace();
+
+        let placement = parts.next().unwrap_or_default();
+        let (placement, pockets) = placement.split_once('[').unwrap_or((placement, ""));
+
+        for (rank, row) in placement.split('/').enumerate() {
+            let mut file = 0;
+
+            for c in row.chars() {
+                if let Some(empty) = c.to_digit(10) {
+                    file += empty as usize;
+                    continue;
+                }
+
+                if c == '~' {
+                    if variant == Variant::Crazyhouse && file > 0 {
+                        board.promoted |= Square::ALL[(7 - rank) * 8 + file - 1].bitboard();
+                    }
+                    continue;
+                }
+
+                let side = usize::from(c.is_ascii_lowercase());
+                let piece = PIECE_CHARS[side]
+                    .iter()
+                    .position(|&p| p == c)
+                    .expect("Valid piece in FEN.");
+
+                let square = Square::ALL[(7 - rank) * 8 + file];
+                board.toggle(side, piece + Piece::PAWN, square);
+                file += 1;
+            }
+        }
+
+        for c in pockets.chars() {
+            let side = usize::from(c.is_ascii_lowercase());
+            if let Some(piece) = PIECE_CHARS[side][..5].iter().position(|&p| p == c) {
+                board.pockets[side][piece] += 1;
+            }
+        }
+
+        board.side_to_move = parts.next() == Some("b");
+
+        for c in parts.next().unwrap_or("-").chars() {
+            if variant != Variant::Antichess {
+                board.parse_castle_right(c);
+            }
+        }
+
+        if let Some(square) = parts.next().and_then(Square::parse) {
+            board.en_passant_rank = 1 << (square.file() as u8);
+        }
+
+        board.half_moves = parts.next().and_then(|n| n.parse().ok()).unwrap_or(0);
+        board.full_moves = parts.next().and_then(|n| n.parse().ok()).unwrap_or(1);
+
+        board.check = board.in_check(usize::from(board.side_to_move));
+        board.hash = board.compute_hash();
+        board
+    }
+
+    pub fn fen(&self) -> String {
+        let mut fen = String::new();
+
+        for rank in (0..8).rev() {
+            let mut empty = 0;
+
+            for file in 0..8 {
+                match self.piece_at(Square::ALL[rank * 8 + file]) {
+                    Some((side, piece)) => {
+                        if empty > 0 {
+                            fen.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+
+                        fen.push(PIECE_CHARS[side][piece - Piece::PAWN]);
+                        if self.promoted & Square::ALL[rank * 8 + file].bitboard() != Bitboard::ZERO
+                        {
+                            fen.push('~');
+                        }
+                    }
+                    None => empty += 1,
+                }
+            }
+
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+            }
+
+            if rank > 0 {
+                fen.push('/');
+            }
+        }
+
+        if self.variant == Variant::Crazyhouse {
+            fen.push('[');
+            for (pocket, chars) in self.pockets.iter().zip(PIECE_CHARS) {
+                for piece in (0..5).rev() {
+                    for _ in 0..pocket[piece] {
+                        fen.push(chars[piece]);
+                    }
+                }
+            }
+            fen.push(']');
+        }
+
+        fen.push_str(if self.side_to_move { " b " } else { " w " });
+
+        let castle = [0, 1]
+            .iter()
+            .flat_map(|&side| [(side, 0), (side, 1)])
+            .filter(|&(side, kind)| self.castle_rights & Castle::SIDES[side][kind] != 0)
+            .map(|(side, kind)| self.castle_char(side, kind))
+            .collect::<String>();
+        fen.push_str(if castle.is_empty() { "-" } else { &castle });
+
+        match self.en_passant_rank {
+            0 => fen.push_str(" -"),
+            files => {
+                let rank = if self.side_to_move {
+                    Rank::Third
+                } else {
+                    Rank::Sixth
+                };
+                fen.push_str(&format!(
+                    " {}{}",
+                    File::ALL[files.trailing_zeros() as usize],
+                    rank
+                ));
+            }
+        }
+
+        fen.push_str(&format!(" {} {}", self.half_moves, self.full_moves));
+        fen
+    }
+
+    /// Reads a castling right in X-FEN, where `K` and `Q` stand for the outermost rook on either
+    /// side of the king, or in Shredder-FEN, which names the file of the rook.
+    fn parse_castle_right(&mut self, c: char) {
+        let side = usize::from(c.is_ascii_lowercase());
+        let rank = if side == 0 { 0 } else { 7 };
+
+        let king = self.bitboard(side, Piece::KING);
+        let rooks = self.bitboard(side, Piece::ROOK);
+        if king == Bitboard::ZERO {
+            return;
+        }
+
+        let king_file = king.trailing_zeros() as u8 & 7;
+        let rook_on =
+            |file: u8| rooks & Square::ALL[rank * 8 + file as usize].bitboard() != Bitboard::ZERO;
+
+        let (kind, file) = match c.to_ascii_uppercase() {
+            'K' => (0, (king_file + 1..8).rev().find(|&file| rook_on(file))),
+            'Q' => (1, (0..king_file).find(|&file| rook_on(file))),
+            file @ 'A'..='H' => {
+                let file = file as u8 - b'A';
+                (
+                    usize::from(file < king_file),
+                    Some(file).filter(|&file| rook_on(file)),
+                )
+            }
+            _ => return,
+        };
+
+        if let Some(file) = file {
+            self.rook_files[side][kind] = file;
+            self.castle_rights |= Castle::SIDES[side][kind];
+        }
+    }
+
+    /// Castling right in X-FEN, which only names the file if the rook is not the outermost one.
+    fn castle_char(&self, side: usize, kind: usize) -> char {
+        let rank = if side == 0 { 0 } else { 7 };
+        let file = self.rook_files[side][kind];
+
+        let king_file = self.bitboard(side, Piece::KING).trailing_zeros() as u8 & 7;
+        let outer = if kind == 0 {
+            file + 1..8
+        } else {
+            0..file.min(king_file)
+        };
+
+        let rooks = self.bitboard(side, Piece::ROOK);
+        let outermost = outer
+            .into_iter()
+            .all(|file| rooks & Square::ALL[rank * 8 + file as usize].bitboard() == Bitboard::ZERO);
+
+        let c = if outermost {
+            ['K', 'Q'][kind]
+        } else {
+            char::from(b'A' + file)
+        };
+
+        if side == 0 {
+            c
+        } else {
+            c.to_ascii_lowercase()
+        }
+    }
+
+    /// Square of the rook that castles with the king of `side`, kingside first.
+    pub fn castle_rook(&self, side: usize, kind: usize) -> Square {
+        let rank = if side == 0 { 0 } else { 56 };
+        Square::ALL[rank + self.rook_files[side][kind] as usize]
+    }
+
+    pub fn display(&self) -> String {
+        let mut board = String::new();
+
+        for rank in (0..8).rev() {
+            board.push_str("+---+---+---+---+---+---+---+---+\n");
+
+            for file in 0..8 {
+                let cell = match self.piece_at(Square::ALL[rank * 8 + file]) {
+                    Some((side, piece)) => PIECE_CHARS[side][piece - Piece::PAWN],
+                    None => ' ',
+                };
+
+                board.push_str(&format!("| {} ", cell));
+            }
+
+            board.push_str(&format!("| {}\n", rank + 1));
+        }
+
+        board.push_str("+---+---+---+---+---+---+---+---+\n");
+        board.push_str("  a   b   c   d   e   f   g   h");
+        board
+    }
+
+    pub fn piece_at(&self, square: Square) -> Option<(usize, usize)> {
+        let bitboard = square.bitboard();
+
+        let side = if self.bitboards[0] & bitboard != Bitboard::ZERO {
+            0
+        } else if self.bitboards[1] & bitboard != Bitboard::ZERO {
+            1
+        } else {
+            return None;
+        };
+
+        (Piece::PAWN..=Piece::KING)
+            .find(|&piece| self.bitboards[piece] & bitboard != Bitboard::ZERO)
+            .map(|piece| (side, piece))
+    }
+
+    pub fn bitboard(&self, side: usize, piece: usize) -> Bitboard {
+        self.bitboards[side] & self.bitboards[piece]
+    }
+
+    pub fn occupancy(&self) -> Bitboard {
+        self.bitboards[0] | self.bitboards[1]
+    }
+
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    pub fn half_moves(&self) -> u8 {
+        self.half_moves
+    }
+
+    pub fn full_moves(&self) -> u16 {
+        self.full_moves
+    }
+
+    pub fn is_check(&self) -> bool {
+        self.check
+    }
+
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    fn set_pocket(&mut self, side: usize, piece: usize, count: u8) {
+        let pocket = &mut self.pockets[side][piece - Piece::PAWN];
+
+        self.hash ^= zobrist::pocket(side, piece, *pocket) ^ zobrist::pocket(side, piece, count);
+        *pocket = count;
+    }
+
+    /// Plays a pseudo-legal move and returns whether it left the own king in check, or in Atomic
+    /// exploded it.
+    pub fn make_move(&mut self, mov: Move) -> (bool, Changes) {
+        let side = usize::from(self.side_to_move);
+        let mut changes = Changes::default();
+
+        let piece = match mov.dropped() {
+            Some(piece) => piece,
+            None => self.piece_at(mov.from).expect("Piece on from square.").1,
+        };
+
+        self.hash ^=
+            zobrist::castle(self.castle_rights) ^ zobrist::en_passant(self.en_passant_rank);
+
+        if mov.dropped().is_some() {
+            self.set_pocket(side, piece, self.pockets[side][piece - Piece::PAWN] - 1);
+            self.toggle(side, piece, mov.to);
+            changes.add(side, piece, mov.to);
+        } else if mov.flag == Flag::KING_CASTLE || mov.flag == Flag::QUEEN_CASTLE {
+            // The move goes from the king to its own rook, which covers every Chess960 setup.
+            let kind = usize::from(mov.flag == Flag::QUEEN_CASTLE);
+            let rank = mov.from as usize & 56;
+            let king = Square::ALL[rank + Castle::KING_FILES[kind]];
+            let rook = Square::ALL[rank + Castle::ROOK_FILES[kind]];
+
+            self.toggle(side, Piece::KING, mov.from);
+            self.toggle(side, Piece::ROOK, mov.to);
+            self.toggle(side, Piece::KING, king);
+            self.toggle(side, Piece::ROOK, rook);
+
+            changes.remove(side, Piece::KING, mov.from);
+            changes.remove(side, Piece::ROOK, mov.to);
+            changes.add(side, Piece::KING, king);
+            changes.add(side, Piece::ROOK, rook);
+        } else {
+            let captured = if mov.flag == Flag::EN_PASSANT {
+                let square = mov.to.shift::<8>(!self.side_to_move);
+                self.toggle(side ^ 1, Piece::PAWN, square);
+                changes.remove(side ^ 1, Piece::PAWN, square);
+
+                Some(Piece::PAWN)
+            } else if mov.flag & Flag::CAPTURE != 0 {
+                let (_, captured) = self.piece_at(mov.to).expect("Piece on capture square.");
+                self.toggle(side ^ 1, captured, mov.to);
+                changes.remove(side ^ 1, captured, mov.to);
+
+                Some(captured)
+            } else {
+                None
+            };
+
+            self.toggle(side, piece, mov.from);
+            changes.remove(side, piece, mov.from);
+
+            let placed = mov.promotion().unwrap_or(piece);
+            self.toggle(side, placed, mov.to);
+            changes.add(side, placed, mov.to);
+
+            match (self.variant, captured) {
+                (Variant::Crazyhouse, _) => {
+                    let (from, to) = (mov.from.bitboard(), mov.to.bitboard());
+
+                    if let Some(captured) = captured {
+                        let captured = if self.promoted & to != Bitboard::ZERO {
+                            Piece::PAWN
+                        } else {
+                            captured
+                        };
+                        self.set_pocket(
+                            side,
+                            captured,
+                            self.pockets[side][captured - Piece::PAWN] + 1,
+                        );
+                    }
+
+                    let promoted =
+                        mov.promotion().is_some() || self.promoted & from != Bitboard::ZERO;
+                    self.promoted &= !(from | to);
+                    if promoted {
+                        self.promoted |= to;
+                    }
+                }
+                (Variant::Atomic, Some(_)) => self.explode(mov.to, &mut changes),
+                _ => {}
+            }
+        }
+
+        if piece == Piece::KING {
+            self.castle_rights &= !Castle::RIGHTS[side];
+        }
+
+        // Besides moving, a rook or king loses its castling right by being captured or exploded.
+        for (side, kind) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            let rook = self.castle_rook(side, kind);
+            if mov.from == rook
+                || mov.to == rook
+                || self.bitboard(side, Piece::ROOK) & rook.bitboard() == Bitboard::ZERO
+                || self.bitboard(side, Piece::KING) == Bitboard::ZERO
+            {
+                self.castle_rights &= !Castle::SIDES[side][kind];
+            }
+        }
+
+        self.en_passant_rank = if mov.flag == Flag::DOUBLE_PAWN {
+            1 << (mov.from.file() as u8)
+        } else {
+            0
+        };
+
+        self.half_moves = if piece == Piece::PAWN || mov.flag & Flag::CAPTURE != 0 {
+            0
+        } else {
+            self.half_moves.saturating_add(1)
+        };
+
+        self.hash ^= zobrist::castle(self.castle_rights)
+            ^ zobrist::en_passant(self.en_passant_rank)
+            ^ zobrist::side();
+
+        if self.side_to_move {
+            self.full_moves += 1;
+        }
+
+        let illegal = match self.variant {
+            Variant::Atomic if self.bitboard(side, Piece::KING) == Bitboard::ZERO => true,
+            _ => self.in_check(side),
+        };
+
+        self.side_to_move ^= true;
+        self.check = self.in_check(side ^ 1);
+
+        (illegal, changes)
+    }
+
+    /// Removes the piece that captured on `square` in Atomic, together with every piece but pawns
+    /// next to it.
+    fn explode(&mut self, square: Square, changes: &mut Changes) {
+        let mut exploded = square.bitboard()
+            | get_king_moves(square) & self.occupancy() & !self.bitboards[Piece::PAWN];
+
+        bitboard_loop!(exploded, square, {
+            let (side, piece) = self.piece_at(square).expect("Piece on exploded square.");
+            self.toggle(side, piece, square);
+            changes.remove(side, piece, square);
+        });
+    }
+
+    pub fn gen_legal_moves(&self) -> Vec<Move> {
+        let mut moves = self.gen_moves();
+
+        if self.variant != Variant::Antichess {
+            moves.retain(|&mov| !{ *self }.make_move(mov).0);
+        } else if moves.iter().any(|mov| mov.flag & Flag::CAPTURE != 0) {
+            moves.retain(|mov| mov.flag & Flag::CAPTURE != 0);
+        }
+
+        moves
+    }
+
+    fn promotions(&self) -> &'static [usize] {
+        if self.variant == Variant::Antichess {
+            &PROMOTIONS
+        } else {
+            &PROMOTIONS[..4]
+        }
+    }
+
     pub fn gen_moves(&self) -> Vec<Move> {
         let mut moves = Vec::with_capacity(35);
 
@@ -61,11 +587,11 @@ impl Board {
                 })
             );
             bitboard_loop!(promo_mask, to, {
-                for piece in Piece::KNIGHT..=Piece::QUEEN {
+                for &piece in self.promotions() {
                     moves.push(Move {
                         from,
                         to,
-                        flag: (piece as u8 - 3) | Flag::CAPTURE | Flag::PROMOTION,
+                        flag: promotion_flag(piece) | Flag::CAPTURE,
                     });
                 }
             });
@@ -133,65 +659,207 @@ impl Board {
 
         let mut promo_mask = pawn_mask & Rank::PROMO[side].bitboard();
         bitboard_loop!(promo_mask, from, {
-            for piece in Piece::KNIGHT..=Piece::QUEEN {
+            for &piece in self.promotions() {
                 moves.push(Move {
                     from,
                     to: from.shift::<8>(self.side_to_move),
-                    flag: (piece as u8 - 3) | Flag::PROMOTION,
+                    flag: promotion_flag(piece),
                 });
             }
         });
 
+        if self.variant == Variant::Crazyhouse {
+            for piece in Piece::PAWN..=Piece::QUEEN {
+                if self.pockets[side][piece - Piece::PAWN] == 0 {
+                    continue;
+                }
+
+                let mut targets = !occupancy;
+                if piece == Piece::PAWN {
+                    targets &= !(Rank::First.bitboard() | Rank::Eighth.bitboard());
+                }
+
+                bitboard_loop!(targets, to, moves.push(Move::drop(piece, to)));
+            }
+        }
+
         if self.in_check(side) || self.castle_rights & Castle::RIGHTS[side] == 0 {
             return moves;
         }
 
-        let (king, rook, from, to) = if self.side_to_move {
-            (Castle::BLACK_KING, Square::F8, Square::E8, Square::G8)
-        } else {
-            (Castle::WHITE_KING, Square::F1, Square::E1, Square::G1)
-        };
+        let from = (self.bitboards[Piece::KING] & self.bitboards[side]).trailing_zeros();
+        let rank = from as usize & 56;
 
-        if occupancy & Castle::MASK[side][0] == Bitboard::ZERO
-            && self.castle_rights & king != 0
-            && !self.square_attacked(rook, side, occupancy)
-        {
-            moves.push(Move {
-                from,
-                to,
-                flag: Flag::KING_CASTLE,
+        for kind in 0..2 {
+            if self.castle_rights & Castle::SIDES[side][kind] == 0 {
+                continue;
+            }
+
+            let rook = self.castle_rook(side, kind);
+            let king_to = Square::ALL[rank + Castle::KING_FILES[kind]];
+            let rook_to = Square::ALL[rank + Castle::ROOK_FILES[kind]];
+
+            // Every square either piece crosses must be empty apart from the two castling pieces,
+            // and the king must not pass through check.
+            let blockers = occupancy ^ from.bitboard() ^ rook.bitboard();
+            let path = span(from, king_to) | span(rook, rook_to);
+            if blockers & path != Bitboard::ZERO {
+                continue;
+            }
+
+            let mut transit = span(from, king_to) ^ from.bitboard();
+            let mut attacked = false;
+            bitboard_loop!(transit, square, {
+                attacked |= self.king_attacked(square, side, blockers);
             });
+
+            if !attacked {
+                moves.push(Move {
+                    from,
+                    to: rook,
+                    flag: [Flag::KING_CASTLE, Flag::QUEEN_CASTLE][kind],
+                });
+            }
         }
 
-        let (king, rook, to) = if self.side_to_move {
-            (Castle::BLACK_QUEEN, Square::D8, Square::C8)
-        } else {
-            (Castle::WHITE_QUEEN, Square::D1, Square::C1)
-        };
+        moves
+    }
+
+    fn toggle(&mut self, side: usize, piece: usize, square: Square) {
+        let bitboard = square.bitboard();
+
+        self.bitboards[side] ^= bitboard;
+        self.bitboards[piece] ^= bitboard;
+        self.hash ^= zobrist::piece(side, piece, square);
+    }
+
+    fn compute_hash(&self) -> u64 {
+        let mut hash =
+            zobrist::castle(self.castle_rights) ^ zobrist::en_passant(self.en_passant_rank);
+        if self.side_to_move {
+            hash ^= zobrist::side();
+        }
+
+        for side in 0..2 {
+            for piece in Piece::PAWN..=Piece::QUEEN {
+                hash ^= zobrist::pocket(side, piece, self.pockets[side][piece - Piece::PAWN]);
+            }
+        }
+
+        for square in Square::ALL {
+            if let Some((side, piece)) = self.piece_at(square) {
+                hash ^= zobrist::piece(side, piece, square);
+            }
+        }
 
-        if occupancy & Castle::MASK[side][1] == Bitboard::ZERO
-            && self.castle_rights & king != 0
-            && !self.square_attacked(rook, side, occupancy)
+        hash
+    }
+
+    /// All pieces of both sides attacking `square` with the given occupancy.
+    pub fn attackers(&self, square: Square, occupancy: Bitboard) -> Bitboard {
+        let diagonal = self.bitboards[Piece::BISHOP] | self.bitboards[Piece::QUEEN];
+        let orthogonal = self.bitboards[Piece::ROOK] | self.bitboards[Piece::QUEEN];
+
+        get_pawn_attacks(square, 0) & self.bitboard(1, Piece::PAWN)
+            | get_pawn_attacks(square, 1) & self.bitboard(0, Piece::PAWN)
+            | get_knight_moves(square) & self.bitboards[Piece::KNIGHT]
+            | get_king_moves(square) & self.bitboards[Piece::KING]
+            | get_bishop_moves(square, occupancy) & diagonal
+            | get_rook_moves(square, occupancy) & orthogonal
+    }
+
+    /// Static exchange evaluation of the sequence of captures on the target square of `mov`,
+    /// in centipawns for the side to move.
+    pub fn see(&self, mov: Move) -> i32 {
+        const VALUES: [i32; 8] = [0, 0, 100, 300, 300, 500, 900, 20_000];
+
+        if mov.flag == Flag::KING_CASTLE
+            || mov.flag == Flag::QUEEN_CASTLE
+            || mov.dropped().is_some()
         {
-            moves.push(Move {
-                from,
-                to,
-                flag: Flag::QUEEN_CASTLE,
-            });
+            return 0;
         }
 
-        moves
+        let (_, mut piece) = self.piece_at(mov.from).expect("Piece on from square.");
+
+        let mut gain = [0; 32];
+        let mut occupancy = self.occupancy();
+
+        if mov.flag == Flag::EN_PASSANT {
+            gain[0] = VALUES[Piece::PAWN];
+            occupancy ^= mov.to.shift::<8>(!self.side_to_move).bitboard();
+        } else if let Some((_, captured)) = self.piece_at(mov.to) {
+            gain[0] = VALUES[captured];
+        }
+
+        if let Some(promotion) = mov.promotion() {
+            piece = promotion;
+            gain[0] += VALUES[piece] - VALUES[Piece::PAWN];
+        }
+
+        let mut side = usize::from(self.side_to_move);
+        let mut from = mov.from.bitboard();
+        let mut depth = 0;
+
+        while depth < gain.len() - 1 {
+            depth += 1;
+            gain[depth] = VALUES[piece] - gain[depth - 1];
+            if (-gain[depth - 1]).max(gain[depth]) < 0 {
+                break;
+            }
+
+            occupancy ^= from;
+            side ^= 1;
+
+            let attackers = self.attackers(mov.to, occupancy) & occupancy & self.bitboards[side];
+            let Some(next) = (Piece::PAWN..=Piece::KING)
+                .find(|&piece| attackers & self.bitboards[piece] != Bitboard::ZERO)
+            else {
+                break;
+            };
+
+            let candidates = attackers & self.bitboards[next];
+            from = candidates & Bitboard(candidates.0.wrapping_neg());
+            piece = next;
+        }
+
+        while depth > 1 {
+            depth -= 1;
+            gain[depth - 1] = -(-gain[depth - 1]).max(gain[depth]);
+        }
+
+        gain[0]
+    }
+
+    pub fn in_check(&self, side: usize) -> bool {
+        let king = self.bitboard(side, Piece::KING);
+
+        match self.variant {
+            Variant::Antichess => false,
+            // A missing king was exploded, which ends the game instead.
+            Variant::Atomic
+                if king == Bitboard::ZERO
+                    || self.bitboard(side ^ 1, Piece::KING) == Bitboard::ZERO =>
+            {
+                false
+            }
+            _ => self.king_attacked(king.trailing_zeros(), side, self.occupancy()),
+        }
     }
 
-    fn in_check(&self, side: usize) -> bool {
-        self.square_attacked(
-            (self.bitboards[Piece::KING] & self.bitboards[side]).trailing_zeros() as Square,
-            side,
-            self.bitboards[0] | self.bitboards[1],
-        )
+    /// Whether a king of `side` would be attacked on `square`. In Atomic it is safe next to the
+    /// opponent king, as capturing it would explode both kings.
+    fn king_attacked(&self, square: Square, side: usize, occupancy: Bitboard) -> bool {
+        if self.variant == Variant::Atomic
+            && get_king_moves(square) & self.bitboard(side ^ 1, Piece::KING) != Bitboard::ZERO
+        {
+            return false;
+        }
+
+        self.square_attacked(square, side, occupancy)
     }
 
-    fn square_attacked(&self, square: Square, side: usize, occupancy: Bitboard) -> bool {
+    pub fn square_attacked(&self, square: Square, side: usize, occupancy: Bitboard) -> bool {
         (self.bitboards[Piece::KNIGHT] & get_knight_moves(square)
             | self.bitboards[Piece::KING] & get_king_moves(square)
             | self.bitboards[Piece::PAWN] & get_pawn_attacks(square, side)
@@ -203,3 +871,189 @@ impl Board {
             != Bitboard::ZERO
     }
 }
+
+fn promotion_flag(piece: usize) -> u8 {
+    if piece == Piece::KING {
+        Flag::PROMOTION | Flag::KING_PROMOTION
+    } else {
+        (piece - Piece::KNIGHT) as u8 | Flag::PROMOTION
+    }
+}
+
+/// Squares on the rank of `a` from `a` to `b`, both included.
+fn span(a: Square, b: Square) -> Bitboard {
+    let (a, b) = (a as u64, b as u64);
+    Bitboard((u64::MAX >> (63 - a.max(b))) & (u64::MAX << a.min(b)))
+}
+
+#[cfg(test)]
+mod board_test {
+    use super::{Board, Variant, STARTPOS};
+
+    const PERFT_960: [(&str, &[u64]); 3] = [
+        (
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+            &[21, 528, 12189],
+        ),
+        (
+            "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+            &[21, 807, 18002],
+        ),
+        (
+            "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9",
+            &[20, 479, 10471],
+        ),
+    ];
+
+    const PERFT: [(&str, &[u64]); 5] = [
+        (STARTPOS, &[20, 400, 8902]),
+        (
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            &[48, 2039, 97862],
+        ),
+        (
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            &[14, 191, 2812],
+        ),
+        (
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            &[6, 264, 9467],
+        ),
+        (
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            &[44, 1486, 62379],
+        ),
+    ];
+
+    const PERFT_VARIANTS: [(Variant, &str, &[u64]); 7] = [
+        (
+            Variant::Crazyhouse,
+            "2k5/8/8/8/8/8/8/4K3[QRBNPqrbnp] w - - 0 1",
+            &[301, 75353],
+        ),
+        (
+            Variant::Crazyhouse,
+            "r1bqk2r/pppp1ppp/2n1p3/4P3/1b1Pn3/2NB1N2/PPP2PPP/R1BQK2R[] b KQkq - 0 1",
+            &[42, 1347, 58057],
+        ),
+        (
+            Variant::Crazyhouse,
+            "4k3/1Q~6/8/8/4b3/8/Kpp5/8[] b - - 0 1",
+            &[20, 360, 5445],
+        ),
+        (
+            Variant::Atomic,
+            "rn2kb1r/1pp1p2p/p2q1pp1/3P4/2P3b1/4PN2/PP3PPP/R2QKB1R b KQkq - 0 1",
+            &[40, 1238, 45237],
+        ),
+        (
+            Variant::Atomic,
+            "rn1qkb1r/p5pp/2p5/3p4/N3P3/5P2/PPP4P/R1BQK3 w Qkq - 0 1",
+            &[28, 833, 23353],
+        ),
+        (
+            Variant::Antichess,
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1",
+            &[20, 400, 8067],
+        ),
+        (
+            Variant::Antichess,
+            "8/1p6/8/8/8/8/P7/8 w - - 0 1",
+            &[2, 4, 4, 3, 1],
+        ),
+    ];
+
+    fn perft(board: &Board, depth: usize) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        board
+            .gen_legal_moves()
+            .into_iter()
+            .map(|mov| {
+                let mut board = *board;
+                board.make_move(mov);
+
+                perft(&board, depth - 1)
+            })
+            .sum()
+    }
+
+    #[test]
+    fn assert_see() {
+        const SEE: [(&str, &str, i32); 4] = [
+            (
+                "1k1r4/1pp4p/p7/4p3/8/P5P1/1PP4P/2K1R3 w - - 0 1",
+                "e1e5",
+                100,
+            ),
+            (
+                "1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3 w - - 0 1",
+                "d3e5",
+                -200,
+            ),
+            ("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1", "e4d5", 100),
+            ("4k3/8/2p5/3p4/4P3/8/8/3QK3 w - - 0 1", "d1d5", -800),
+        ];
+
+        for (fen, mov, expected) in SEE {
+            let board = Board::from_fen(fen);
+            let mov = board
+                .gen_legal_moves()
+                .into_iter()
+                .find(|m| m.to_string() == mov)
+                .unwrap();
+
+            assert_eq!(
+                board.see(mov),
+                expected,
+                "SEE failed for {} in {}",
+                mov,
+                fen
+            );
+        }
+    }
+
+    #[test]
+    fn assert_perft() {
+        for (fen, nodes) in PERFT {
+            let board = Board::from_fen(fen);
+            assert_eq!(board.fen(), fen);
+
+            check_perft(&board, fen, nodes);
+        }
+    }
+
+    #[test]
+    fn assert_perft_chess960() {
+        for (fen, nodes) in PERFT_960 {
+            let board = Board::from_fen(fen);
+            assert!(Board::from_fen(&board.fen()) == board, "{}", board.fen());
+
+            check_perft(&board, fen, nodes);
+        }
+    }
+
+    #[test]
+    fn assert_perft_variants() {
+        for (variant, fen, nodes) in PERFT_VARIANTS {
+            let board = Board::from_variant_fen(fen, variant);
+            assert_eq!(board.fen(), fen);
+
+            check_perft(&board, fen, nodes);
+        }
+    }
+
+    fn check_perft(board: &Board, fen: &str, nodes: &[u64]) {
+        for (depth, &expected) in nodes.iter().enumerate() {
+            assert_eq!(
+                perft(board, depth + 1),
+                expected,
+                "Perft {} failed for {}",
+                depth + 1,
+                fen
+            );
+        }
+    }
+}
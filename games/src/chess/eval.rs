@@ -0,0 +1,469 @@
+use std::{io, path::Path, sync::OnceLock};
+
+use crate::{bitboard_loop, tune};
+
+use super::{
+    board::Board,
+    moves::{
+        get_bishop_moves, get_king_moves, get_knight_moves, get_pawn_attacks, get_queen_moves,
+        get_rook_moves,
+    },
+    types::{bitboard::Bitboard, file::File, rank::Rank, square::Square},
+    util::Piece,
+};
+
+pub const MATERIAL: usize = 0;
+pub const PST: usize = MATERIAL + 5;
+pub const MOBILITY: usize = PST + 6 * 64;
+pub const DOUBLED: usize = MOBILITY + 4;
+pub const ISOLATED: usize = DOUBLED + 1;
+pub const PASSED: usize = ISOLATED + 1;
+pub const KING_SHIELD: usize = PASSED + 8;
+pub const KING_ATTACK: usize = KING_SHIELD + 1;
+pub const NUM_PARAMS: usize = KING_ATTACK + 1;
+
+/// Game phase of the starting position, a knight or bishop counts 1, a rook 2 and a queen 4.
+pub const MAX_PHASE: i32 = 24;
+
+/// Middlegame and endgame weight of a single evaluation term.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Score(pub f32, pub f32);
+
+pub struct Parameters {
+    pub weights: [Score; NUM_PARAMS],
+    /// Centipawns at which the squashed evaluation reaches `2 / (1 + e^-1) - 1`.
+    pub scale: f32,
+}
+
+/// Occurrences of every evaluation term, white minus black, so the evaluation is linear in the
+/// parameters.
+pub struct Trace {
+    pub coefficients: [i16; NUM_PARAMS],
+    pub phase: i32,
+}
+
+static PARAMETERS: OnceLock<Parameters> = OnceLock::new();
+
+pub fn parameters() -> &'static Parameters {
+    PARAMETERS.get_or_init(Parameters::default)
+}
+
+/// Replaces the default weights, which is only possible before the first evaluation.
+pub fn load(path: &Path) -> io::Result<()> {
+    let parameters = Parameters::read(path)?;
+
+    PARAMETERS.set(parameters).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "Evaluation parameters already in use.",
+        )
+    })
+}
+
+impl Parameters {
+    /// Reads the scale followed by the middlegame and endgame weight of every term.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let (scale, values) = tune::read_parameters(path, 2 * NUM_PARAMS)?;
+
+        let mut weights = [Score::default(); NUM_PARAMS];
+        for (weight, value) in weights.iter_mut().zip(values.chunks(2)) {
+            *weight = Score(value[0], value[1]);
+        }
+
+        Ok(Parameters { weights, scale })
+    }
+
+    /// Evaluation in centipawns from the perspective of white.
+    pub fn evaluate(&self, trace: &Trace) -> f32 {
+        let (mg, eg) = trace
+            .coefficients
+            .iter()
+            .zip(self.weights.iter())
+            .filter(|(&coefficient, _)| coefficient != 0)
+            .fold((0.0, 0.0), |(mg, eg), (&coefficient, weight)| {
+                let coefficient = f32::from(coefficient);
+                (mg + coefficient * weight.0, eg + coefficient * weight.1)
+            });
+
+        let phase = trace.phase as f32 / MAX_PHASE as f32;
+        mg * phase + eg * (1.0 - phase)
+    }
+
+    pub fn squash(&self, eval: f32) -> f32 {
+        2.0 / (1.0 + (-eval / self.scale).exp()) - 1.0
+    }
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        let mut weights = [Score::default(); NUM_PARAMS];
+
+        const MATERIAL_MG: [f32; 5] = [82.0, 337.0, 365.0, 477.0, 1025.0];
+        const MATERIAL_EG: [f32; 5] = [94.0, 281.0, 297.0, 512.0, 936.0];
+        for piece in 0..5 {
+            weights[MATERIAL + piece] = Score(MATERIAL_MG[piece], MATERIAL_EG[piece]);
+        }
+
+        for piece in 0..6 {
+            for square in 0..64 {
+                let (mg, eg) = if piece == 5 {
+                    (KING_MG[square ^ 56], KING_EG[square ^ 56])
+                } else {
+                    (
+                        PIECE_SQUARE_MG[piece][square ^ 56],
+                        PIECE_SQUARE_EG[piece][square ^ 56],
+                    )
+                };
+
+                weights[PST + piece * 64 + square] = Score(f32::from(mg), f32::from(eg));
+            }
+        }
+
+        const MOBILITY_WEIGHTS: [Score; 4] = [
+            Score(4.0, 4.0),
+            Score(5.0, 5.0),
+            Score(2.0, 4.0),
+            Score(1.0, 2.0),
+        ];
+        weights[MOBILITY..MOBILITY + 4].copy_from_slice(&MOBILITY_WEIGHTS);
+
+        weights[DOUBLED] = Score(-10.0, -20.0);
+        weights[ISOLATED] = Score(-10.0, -10.0);
+
+        const PASSED_MG: [f32; 8] = [0.0, 5.0, 10.0, 15.0, 25.0, 40.0, 60.0, 0.0];
+        const PASSED_EG: [f32; 8] = [0.0, 10.0, 20.0, 35.0, 60.0, 90.0, 130.0, 0.0];
+        for rank in 0..8 {
+            weights[PASSED + rank] = Score(PASSED_MG[rank], PASSED_EG[rank]);
+        }
+
+        weights[KING_SHIELD] = Score(10.0, 0.0);
+        weights[KING_ATTACK] = Score(-8.0, -2.0);
+
+        Parameters {
+            weights,
+            scale: 400.0,
+        }
+    }
+}
+
+pub fn trace(board: &Board) -> Trace {
+    let mut trace = Trace {
+        coefficients: [0; NUM_PARAMS],
+        phase: 0,
+    };
+
+    let occupancy = board.occupancy();
+
+    for side in 0..2 {
+        let sign = if side == 0 { 1 } else { -1 };
+        let relative = |square: Square| square as usize ^ (56 * side);
+
+        let own = board.bitboard(side, Piece::PAWN)
+            | board.bitboard(side, Piece::KNIGHT)
+            | board.bitboard(side, Piece::BISHOP)
+            | board.bitboard(side, Piece::ROOK)
+            | board.bitboard(side, Piece::QUEEN)
+            | board.bitboard(side, Piece::KING);
+
+        for piece in Piece::PAWN..=Piece::KING {
+            let index = piece - Piece::PAWN;
+
+            let mut pieces = board.bitboard(side, piece);
+            bitboard_loop!(pieces, square, {
+                if piece != Piece::KING {
+                    trace.coefficients[MATERIAL + index] += sign;
+                }
+
+                trace.coefficients[PST + index * 64 + relative(square)] += sign;
+
+                let attacks = match piece {
+                    Piece::KNIGHT => get_knight_moves(square),
+                    Piece::BISHOP => get_bishop_moves(square, occupancy),
+                    Piece::ROOK => get_rook_moves(square, occupancy),
+                    Piece::QUEEN => get_queen_moves(square, occupancy),
+                    _ => continue,
+                };
+
+                trace.coefficients[MOBILITY + index - 1] += sign * (attacks & !own).count() as i16;
+                trace.phase += [1, 1, 2, 4][index - 1];
+            });
+        }
+
+        trace_pawns(board, side, sign, &mut trace);
+        trace_king(board, side, sign, &mut trace);
+    }
+
+    trace.phase = trace.phase.min(MAX_PHASE);
+    trace
+}
+
+fn trace_pawns(board: &Board, side: usize, sign: i16, trace: &mut Trace) {
+    let pawns = board.bitboard(side, Piece::PAWN);
+    let enemy = board.bitboard(side ^ 1, Piece::PAWN);
+
+    for file in File::ALL {
+        let count = (pawns & file.bitboard()).count() as i16;
+        if count > 1 {
+            trace.coefficients[DOUBLED] += sign * (count - 1);
+        }
+    }
+
+    let mut iter = pawns;
+    bitboard_loop!(iter, square, {
+        let file = square.file() as usize;
+        let adjacent = adjacent_files(file);
+
+        if pawns & adjacent == Bitboard::ZERO {
+            trace.coefficients[ISOLATED] += sign;
+        }
+
+        let rank = square.rank() as usize;
+        let ahead = if side == 0 {
+            Bitboard(u64::MAX.checked_shl(8 * (rank as u32 + 1)).unwrap_or(0))
+        } else {
+            Bitboard((1u64 << (8 * rank)) - 1)
+        };
+
+        if enemy & ahead & (adjacent | File::ALL[file].bitboard()) == Bitboard::ZERO {
+            let relative_rank = if side == 0 { rank } else { 7 - rank };
+            trace.coefficients[PASSED + relative_rank] += sign;
+        }
+    });
+}
+
+fn trace_king(board: &Board, side: usize, sign: i16, trace: &mut Trace) {
+    let king = board.bitboard(side, Piece::KING).trailing_zeros();
+    let zone = get_king_moves(king);
+
+    let file = king.file() as usize;
+    let rank = king.rank() as usize;
+
+    let shield = [1, 2]
+        .iter()
+        .filter_map(|distance| match side {
+            0 => Some(rank + distance).filter(|&rank| rank < 8),
+            _ => rank.checked_sub(*distance),
+        })
+        .fold(Bitboard::ZERO, |shield, rank| {
+            shield | Rank::ALL[rank].bitboard()
+        })
+        & (adjacent_files(file) | File::ALL[file].bitboard());
+
+    trace.coefficients[KING_SHIELD] +=
+        sign * (board.bitboard(side, Piece::PAWN) & shield).count() as i16;
+
+    let occupancy = board.occupancy();
+    let mut attacked = Bitboard::ZERO;
+
+    for piece in Piece::PAWN..Piece::KING {
+        let mut pieces = board.bitboard(side ^ 1, piece);
+        bitboard_loop!(pieces, square, {
+            attacked |= match piece {
+                Piece::PAWN => get_pawn_attacks(square, side ^ 1),
+                Piece::KNIGHT => get_knight_moves(square),
+                Piece::BISHOP => get_bishop_moves(square, occupancy),
+                Piece::ROOK => get_rook_moves(square, occupancy),
+                _ => get_queen_moves(square, occupancy),
+            };
+        });
+    }
+
+    trace.coefficients[KING_ATTACK] += sign * (attacked & zone).count() as i16;
+}
+
+fn adjacent_files(file: usize) -> Bitboard {
+    let mut adjacent = Bitboard::ZERO;
+
+    if file > 0 {
+        adjacent |= File::ALL[file - 1].bitboard();
+    }
+
+    if file < 7 {
+        adjacent |= File::ALL[file + 1].bitboard();
+    }
+
+    adjacent
+}
+
+#[rustfmt::skip]
+const PIECE_SQUARE_MG: [[i16; 64]; 5] = [
+    [
+          0,   0,   0,   0,   0,   0,   0,   0,
+         50,  50,  50,  50,  50,  50,  50,  50,
+         10,  10,  20,  30,  30,  20,  10,  10,
+          5,   5,  10,  25,  25,  10,   5,   5,
+          0,   0,   0,  20,  20,   0,   0,   0,
+          5,  -5, -10,   0,   0, -10,  -5,   5,
+          5,  10,  10, -20, -20,  10,  10,   5,
+          0,   0,   0,   0,   0,   0,   0,   0,
+    ],
+    [
+        -50, -40, -30, -30, -30, -30, -40, -50,
+        -40, -20,   0,   0,   0,   0, -20, -40,
+        -30,   0,  10,  15,  15,  10,   0, -30,
+        -30,   5,  15,  20,  20,  15,   5, -30,
+        -30,   0,  15,  20,  20,  15,   0, -30,
+        -30,   5,  10,  15,  15,  10,   5, -30,
+        -40, -20,   0,   5,   5,   0, -20, -40,
+        -50, -40, -30, -30, -30, -30, -40, -50,
+    ],
+    [
+        -20, -10, -10, -10, -10, -10, -10, -20,
+        -10,   0,   0,   0,   0,   0,   0, -10,
+        -10,   0,   5,  10,  10,   5,   0, -10,
+        -10,   5,   5,  10,  10,   5,   5, -10,
+        -10,   0,  10,  10,  10,  10,   0, -10,
+        -10,  10,  10,  10,  10,  10,  10, -10,
+        -10,   5,   0,   0,   0,   0,   5, -10,
+        -20, -10, -10, -10, -10, -10, -10, -20,
+    ],
+    [
+          0,   0,   0,   0,   0,   0,   0,   0,
+          5,  10,  10,  10,  10,  10,  10,   5,
+         -5,   0,   0,   0,   0,   0,   0,  -5,
+         -5,   0,   0,   0,   0,   0,   0,  -5,
+         -5,   0,   0,   0,   0,   0,   0,  -5,
+         -5,   0,   0,   0,   0,   0,   0,  -5,
+         -5,   0,   0,   0,   0,   0,   0,  -5,
+          0,   0,   0,   5,   5,   0,   0,   0,
+    ],
+    [
+        -20, -10, -10,  -5,  -5, -10, -10, -20,
+        -10,   0,   0,   0,   0,   0,   0, -10,
+        -10,   0,   5,   5,   5,   5,   0, -10,
+         -5,   0,   5,   5,   5,   5,   0,  -5,
+          0,   0,   5,   5,   5,   5,   0,  -5,
+        -10,   5,   5,   5,   5,   5,   0, -10,
+        -10,   0,   5,   0,   0,   0,   0, -10,
+        -20, -10, -10,  -5,  -5, -10, -10, -20,
+    ],
+];
+
+/// Pawns gain from advancing and the other pieces from centralising, as the king is no longer
+/// in danger.
+#[rustfmt::skip]
+const PIECE_SQUARE_EG: [[i16; 64]; 5] = [
+    [
+          0,   0,   0,   0,   0,   0,   0,   0,
+         80,  80,  80,  80,  80,  80,  80,  80,
+         50,  50,  50,  50,  50,  50,  50,  50,
+         30,  30,  30,  30,  30,  30,  30,  30,
+         15,  15,  15,  15,  15,  15,  15,  15,
+          5,   5,   5,   5,   5,   5,   5,   5,
+          0,   0,   0,   0,   0,   0,   0,   0,
+          0,   0,   0,   0,   0,   0,   0,   0,
+    ],
+    [
+        -40, -30, -20, -20, -20, -20, -30, -40,
+        -30, -15,  -5,   0,   0,  -5, -15, -30,
+        -20,  -5,  10,  15,  15,  10,  -5, -20,
+        -20,   0,  15,  20,  20,  15,   0, -20,
+        -20,   0,  15,  20,  20,  15,   0, -20,
+        -20,  -5,  10,  15,  15,  10,  -5, -20,
+        -30, -15,  -5,   0,   0,  -5, -15, -30,
+        -40, -30, -20, -20, -20, -20, -30, -40,
+    ],
+    [
+        -15, -10, -10, -10, -10, -10, -10, -15,
+        -10,  -5,   0,   0,   0,   0,  -5, -10,
+        -10,   0,   5,   5,   5,   5,   0, -10,
+        -10,   0,   5,  10,  10,   5,   0, -10,
+        -10,   0,   5,  10,  10,   5,   0, -10,
+        -10,   0,   5,   5,   5,   5,   0, -10,
+        -10,  -5,   0,   0,   0,   0,  -5, -10,
+        -15, -10, -10, -10, -10, -10, -10, -15,
+    ],
+    [
+          5,   5,   5,   5,   5,   5,   5,   5,
+         15,  15,  15,  15,  15,  15,  15,  15,
+          0,   0,   0,   0,   0,   0,   0,   0,
+          0,   0,   0,   0,   0,   0,   0,   0,
+          0,   0,   0,   0,   0,   0,   0,   0,
+          0,   0,   0,   0,   0,   0,   0,   0,
+          0,   0,   0,   0,   0,   0,   0,   0,
+          0,   0,   0,   0,   0,   0,   0,   0,
+    ],
+    [
+        -20, -10, -10,  -5,  -5, -10, -10, -20,
+        -10,   0,   5,   5,   5,   5,   0, -10,
+        -10,   5,  10,  10,  10,  10,   5, -10,
+         -5,   5,  10,  15,  15,  10,   5,  -5,
+         -5,   5,  10,  15,  15,  10,   5,  -5,
+        -10,   5,  10,  10,  10,  10,   5, -10,
+        -10,   0,   5,   5,   5,   5,   0, -10,
+        -20, -10, -10,  -5,  -5, -10, -10, -20,
+    ],
+];
+
+#[rustfmt::skip]
+const KING_MG: [i16; 64] = [
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -20, -30, -30, -40, -40, -30, -30, -20,
+    -10, -20, -20, -20, -20, -20, -20, -10,
+     20,  20,   0,   0,   0,   0,  20,  20,
+     20,  30,  10,   0,   0,  10,  30,  20,
+];
+
+#[rustfmt::skip]
+const KING_EG: [i16; 64] = [
+    -50, -40, -30, -20, -20, -30, -40, -50,
+    -30, -20, -10,   0,   0, -10, -20, -30,
+    -30, -10,  20,  30,  30,  20, -10, -30,
+    -30, -10,  30,  40,  40,  30, -10, -30,
+    -30, -10,  30,  40,  40,  30, -10, -30,
+    -30, -10,  20,  30,  30,  20, -10, -30,
+    -30, -30,   0,   0,   0,   0, -30, -30,
+    -50, -30, -30, -30, -30, -30, -30, -50,
+];
+
+#[cfg(test)]
+mod eval_test {
+    use super::{parameters, trace, Score, PST};
+    use crate::chess::board::Board;
+
+    const POSITIONS: [(&str, &str); 3] = [
+        (
+            "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+            "rnbqkb1r/pppp1ppp/5n2/4p3/4P3/2N5/PPPP1PPP/R1BQKBNR b KQkq - 2 3",
+        ),
+        (
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "8/4p1p1/8/1r3P1K/kp5R/3P4/2P5/8 b - - 0 1",
+        ),
+        (
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/pppbbppp/2n2q1P/1P2p3/3pn3/BN2PNP1/P1PPQPB1/R3K2R b KQkq - 0 1",
+        ),
+    ];
+
+    #[test]
+    fn assert_eval_symmetry() {
+        let parameters = parameters();
+
+        for (white, black) in POSITIONS {
+            let white = parameters.evaluate(&trace(&Board::from_fen(white)));
+            let black = parameters.evaluate(&trace(&Board::from_fen(black)));
+
+            assert!((white + black).abs() < 1e-3, "{} != {}", white, -black);
+        }
+    }
+
+    #[test]
+    fn assert_phased_tables() {
+        let weights = &parameters().weights;
+
+        // Every piece is placed differently once the queens and most pieces are gone.
+        for piece in 0..6 {
+            let table = &weights[PST + piece * 64..PST + (piece + 1) * 64];
+            assert!(table.iter().any(|&Score(mg, eg)| mg != eg));
+        }
+
+        // An advanced pawn is worth more in the endgame, a castled king less.
+        let (pawn, king) = (weights[PST + 48], weights[PST + 5 * 64 + 6]);
+        assert!(pawn.1 > pawn.0);
+        assert!(king.1 < king.0);
+    }
+}
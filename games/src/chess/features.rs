@@ -0,0 +1,144 @@
+use mentor::network::accumulator::{Accumulator, FeatureTransformer};
+
+use super::{
+    board::{Board, Changes},
+    types::square::Square,
+    util::Piece,
+};
+
+pub const INPUTS: usize = 768;
+pub const HIDDEN: usize = 256;
+
+pub type Transformer = FeatureTransformer<INPUTS, HIDDEN>;
+
+/// Piece-square feature seen from `perspective`, which flips the board and the colours for black.
+#[inline]
+pub fn index(perspective: usize, side: usize, piece: usize, square: Square) -> usize {
+    let square = if perspective == 1 {
+        square.flip()
+    } else {
+        square
+    };
+
+    usize::from(side != perspective) * 384 + (piece - Piece::PAWN) * 64 + square as usize
+}
+
+pub fn active(board: &Board, perspective: usize) -> Vec<usize> {
+    Square::ALL
+        .iter()
+        .filter_map(|&square| {
+            board
+                .piece_at(square)
+                .map(|(side, piece)| index(perspective, side, piece, square))
+        })
+        .collect()
+}
+
+/// First layer outputs from the perspective of white and black.
+#[derive(Copy, Clone)]
+pub struct Accumulators([Accumulator<HIDDEN>; 2]);
+
+impl Accumulators {
+    pub fn refresh(transformer: &Transformer, board: &Board) -> Self {
+        Accumulators([0, 1].map(|perspective| transformer.refresh(&active(board, perspective))))
+    }
+
+    pub fn update(&mut self, transformer: &Transformer, changes: &Changes) {
+        for (perspective, accumulator) in self.0.iter_mut().enumerate() {
+            for &(side, piece, square) in changes.removed() {
+                transformer.remove(accumulator, index(perspective, side, piece, square));
+            }
+
+            for &(side, piece, square) in changes.added() {
+                transformer.add(accumulator, index(perspective, side, piece, square));
+            }
+        }
+    }
+
+    /// Takes back an `update` with the same changes.
+    pub fn revert(&mut self, transformer: &Transformer, changes: &Changes) {
+        for (perspective, accumulator) in self.0.iter_mut().enumerate() {
+            for &(side, piece, square) in changes.added() {
+                transformer.remove(accumulator, index(perspective, side, piece, square));
+            }
+
+            for &(side, piece, square) in changes.removed() {
+                transformer.add(accumulator, index(perspective, side, piece, square));
+            }
+        }
+    }
+
+    pub fn get(&self, perspective: usize) -> &Accumulator<HIDDEN> {
+        &self.0[perspective]
+    }
+}
+
+#[cfg(test)]
+mod features_test {
+    use super::{Accumulators, Transformer, HIDDEN, INPUTS};
+    use crate::chess::board::{Board, STARTPOS};
+    use mentor::network::activation::ReLu;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const POSITIONS: [&str; 4] = [
+        STARTPOS,
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",
+    ];
+
+    fn transformer() -> Box<Transformer> {
+        let mut bytes = Vec::new();
+        for i in 0..(INPUTS + 1) * HIDDEN {
+            bytes.extend_from_slice(&((i as f32 * 0.31).sin() * 0.1).to_le_bytes());
+        }
+
+        Transformer::read(&mut bytes.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn assert_incremental_matches_refresh() {
+        let transformer = transformer();
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for fen in POSITIONS {
+            for _ in 0..5 {
+                let mut board = Board::from_fen(fen);
+                let mut accumulators = Accumulators::refresh(&transformer, &board);
+
+                for _ in 0..100 {
+                    let moves = board.gen_legal_moves();
+                    if moves.is_empty() {
+                        break;
+                    }
+
+                    let mov = moves[rng.gen_range(0..moves.len())];
+                    let previous = accumulators;
+                    let (_, changes) = board.make_move(mov);
+                    accumulators.update(&transformer, &changes);
+
+                    // Taking the move back has to restore the accumulators before it.
+                    let mut reverted = accumulators;
+                    reverted.revert(&transformer, &changes);
+
+                    let expected = Accumulators::refresh(&transformer, &board);
+                    for (actual, expected) in [(&accumulators, &expected), (&reverted, &previous)] {
+                        for perspective in 0..2 {
+                            let actual = actual.get(perspective).activate::<ReLu>();
+                            let expected = expected.get(perspective).activate::<ReLu>();
+
+                            for (a, b) in actual.into_iter().zip(expected) {
+                                assert!(
+                                    (a - b).abs() < 1e-3,
+                                    "Accumulators diverged after {} in {}",
+                                    mov,
+                                    board.fen()
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,10 @@
 use crate::chess::types::bitboard::Bitboard;
 use crate::chess::types::file::File;
-use crate::chess::util::Flag;
+use crate::chess::util::{Castle, Flag, Piece};
 use crate::lookup_table;
 
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use super::types::square::Square;
 
 #[derive(Copy, Clone, PartialEq, Eq)]
@@ -12,32 +14,76 @@ pub struct Move {
     pub flag: u8,
 }
 
+impl Move {
+    /// Drop of `piece` from the pocket on the empty square `to`.
+    pub fn drop(piece: usize, to: Square) -> Self {
+        Move {
+            from: to,
+            to,
+            flag: Flag::DROP | ((piece - Piece::PAWN) as u8) << 5,
+        }
+    }
+
+    pub fn dropped(self) -> Option<usize> {
+        (self.flag & Flag::DROP != 0).then(|| usize::from(self.flag >> 5) + Piece::PAWN)
+    }
+
+    pub fn promotion(self) -> Option<usize> {
+        match self.flag {
+            flag if flag & Flag::PROMOTION == 0 => None,
+            flag if flag & Flag::KING_PROMOTION != 0 => Some(Piece::KING),
+            flag => Some(usize::from(flag & 0b11) + Piece::KNIGHT),
+        }
+    }
+}
+
+static CHESS960: AtomicBool = AtomicBool::new(false);
+
+/// Selects how castling moves are written: as king takes rook in Chess960, otherwise by the
+/// square the king moves to.
+pub fn set_chess960(enabled: bool) {
+    CHESS960.store(enabled, Ordering::Relaxed);
+}
+
 impl std::fmt::Display for Move {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        const PIECES_NAME: [char; 4] = ['n', 'b', 'q', 'k'];
+        const PIECES_NAME: [char; 6] = ['p', 'n', 'b', 'r', 'q', 'k'];
+
+        if let Some(piece) = self.dropped() {
+            let piece = PIECES_NAME[piece - Piece::PAWN].to_ascii_uppercase();
+            return write!(f, "{}@{}", piece, self.to);
+        }
 
         let mut promo = String::new();
-        if self.flag & Flag::PROMOTION != 0 {
-            promo.push(PIECES_NAME[(self.flag & 0b11) as usize]);
+        if let Some(piece) = self.promotion() {
+            promo.push(PIECES_NAME[piece - Piece::PAWN]);
         }
 
-        write!(f, "{}{}{}", self.from, self.to, promo)
+        let castle = self.flag == Flag::KING_CASTLE || self.flag == Flag::QUEEN_CASTLE;
+        let to = if castle && !CHESS960.load(Ordering::Relaxed) {
+            let kind = usize::from(self.flag == Flag::QUEEN_CASTLE);
+            Square::ALL[(self.from as usize & 56) + Castle::KING_FILES[kind]]
+        } else {
+            self.to
+        };
+
+        write!(f, "{}{}{}", self.from, to, promo)
     }
 }
 
-impl From<u16> for Move {
-    fn from(mov: u16) -> Self {
+impl From<u32> for Move {
+    fn from(mov: u32) -> Self {
         Move {
             from: Square::ALL[(mov & 0b0000_000000_111111) as usize],
-            to: Square::ALL[(mov & 0b0000_111111_000000) as usize],
-            flag: (mov & 0b1111_000000_000000) as u8,
+            to: Square::ALL[((mov & 0b0000_111111_000000) >> 6) as usize],
+            flag: (mov >> 12) as u8,
         }
     }
 }
 
-impl From<Move> for u16 {
+impl From<Move> for u32 {
     fn from(mov: Move) -> Self {
-        (mov.from as u32 | (mov.to as u32) << 6 | (mov.flag as u32) << 12) as u16
+        mov.from as u32 | (mov.to as u32) << 6 | (mov.flag as u32) << 12
     }
 }
 
@@ -0,0 +1,269 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+    sync::OnceLock,
+};
+
+use mentor::network::{
+    accumulator::FeatureTransformer,
+    activation::{softmax, Identity, ReLu, TanH},
+    layer::{DenseLayer, Layer},
+    vector::Vector,
+};
+
+use super::{
+    board::Board,
+    features::{self, Accumulators, Transformer, HIDDEN, INPUTS},
+    moves::Move,
+    types::square::Square,
+    util::Flag,
+};
+
+const POLICY_HIDDEN: usize = 128;
+
+static VALUE: OnceLock<ValueNetwork> = OnceLock::new();
+static POLICY: OnceLock<PolicyNetwork> = OnceLock::new();
+
+pub fn value() -> Option<&'static ValueNetwork> {
+    VALUE.get()
+}
+
+pub fn policy() -> Option<&'static PolicyNetwork> {
+    POLICY.get()
+}
+
+pub fn load_value(path: &Path) -> io::Result<()> {
+    let network = ValueNetwork::load(path)?;
+
+    VALUE.set(network).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "Value network already loaded.",
+        )
+    })
+}
+
+pub fn load_policy(path: &Path) -> io::Result<()> {
+    let network = PolicyNetwork::load(path)?;
+
+    POLICY.set(network).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "Policy network already loaded.",
+        )
+    })
+}
+
+/// Perspective network: the accumulator of the side to move is followed by the one of the
+/// opponent before the output layer.
+pub struct ValueNetwork {
+    transformer: Box<Transformer>,
+    output: Box<DenseLayer<f32, { 2 * HIDDEN }, 1>>,
+}
+
+impl ValueNetwork {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::read(&mut BufReader::new(File::open(path)?))
+    }
+
+    pub fn read<R: Read>(reader: &mut R) -> io::Result<Self> {
+        Ok(ValueNetwork {
+            transformer: Transformer::read(reader)?,
+            output: DenseLayer::read(reader)?,
+        })
+    }
+
+    pub fn transformer(&self) -> &Transformer {
+        &self.transformer
+    }
+
+    pub fn value(&self, accumulators: &Accumulators, side_to_move: usize) -> f32 {
+        self.output
+            .forward::<TanH>(Self::hidden(accumulators, side_to_move))
+            .0[0]
+    }
+
+    /// Values of several positions, given as their accumulators and side to move, with one pass
+    /// through the output layer.
+    pub fn values(&self, positions: &[(&Accumulators, usize)]) -> Vec<f32> {
+        let hidden: Vec<_> = positions
+            .iter()
+            .map(|&(accumulators, side_to_move)| Self::hidden(accumulators, side_to_move))
+            .collect();
+
+        self.output
+            .forward_batch::<TanH>(&hidden)
+            .into_iter()
+            .map(|output| output.0[0])
+            .collect()
+    }
+
+    fn hidden(accumulators: &Accumulators, side_to_move: usize) -> Vector<f32, { 2 * HIDDEN }> {
+        let mut hidden = Vector([0.0; 2 * HIDDEN]);
+
+        let us = accumulators.get(side_to_move).activate::<ReLu>();
+        let them = accumulators.get(side_to_move ^ 1).activate::<ReLu>();
+        hidden.0[..HIDDEN].copy_from_slice(&us.0);
+        hidden.0[HIDDEN..].copy_from_slice(&them.0);
+
+        hidden
+    }
+}
+
+/// Scores a move as the sum of its from-square, to-square and promotion logits, all seen from
+/// the side to move.
+pub struct PolicyNetwork {
+    transformer: Box<FeatureTransformer<INPUTS, POLICY_HIDDEN>>,
+    from: Box<DenseLayer<f32, POLICY_HIDDEN, 64>>,
+    to: Box<DenseLayer<f32, POLICY_HIDDEN, 64>>,
+    promotion: Box<DenseLayer<f32, POLICY_HIDDEN, 4>>,
+}
+
+impl PolicyNetwork {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::read(&mut BufReader::new(File::open(path)?))
+    }
+
+    pub fn read<R: Read>(reader: &mut R) -> io::Result<Self> {
+        Ok(PolicyNetwork {
+            transformer: FeatureTransformer::read(reader)?,
+            from: DenseLayer::read(reader)?,
+            to: DenseLayer::read(reader)?,
+            promotion: DenseLayer::read(reader)?,
+        })
+    }
+
+    pub fn policy(&self, board: &Board, moves: &[Move]) -> Vec<f32> {
+        let side = usize::from(board.side_to_move);
+
+        let hidden = self
+            .transformer
+            .refresh(&features::active(board, side))
+            .activate::<ReLu>();
+
+        let from = self.from.forward::<Identity>(hidden);
+        let to = self.to.forward::<Identity>(hidden);
+        let promotion = self.promotion.forward::<Identity>(hidden);
+
+        let relative = |square: Square| {
+            if side == 1 {
+                square.flip() as usize
+            } else {
+                square as usize
+            }
+        };
+
+        let logits: Vec<f32> = moves
+            .iter()
+            .map(|mov| {
+                let mut logit = from.0[relative(mov.from)] + to.0[relative(mov.to)];
+                if mov.flag & Flag::PROMOTION != 0 {
+                    logit += promotion.0[usize::from(mov.flag & 0b11)];
+                }
+
+                logit
+            })
+            .collect();
+
+        softmax(&logits)
+    }
+}
+
+#[cfg(test)]
+mod network_test {
+    use super::{PolicyNetwork, ValueNetwork, POLICY_HIDDEN};
+    use crate::chess::{
+        board::{Board, STARTPOS},
+        features::{Accumulators, HIDDEN, INPUTS},
+        Chess,
+    };
+    use mentor::Game;
+
+    const PROMOTIONS: &str = "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1";
+
+    /// Deterministic weights of dense layers with the given inputs and outputs.
+    fn weights(layers: &[(usize, usize)]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        for (i, &(inputs, outputs)) in layers.iter().enumerate() {
+            for j in 0..(inputs + 1) * outputs {
+                bytes.extend_from_slice(&(((j * 7 + i) as f32 * 0.13).sin() * 0.2).to_le_bytes());
+            }
+        }
+
+        bytes
+    }
+
+    #[test]
+    fn assert_masked_policy() {
+        let bytes = weights(&[
+            (INPUTS, POLICY_HIDDEN),
+            (POLICY_HIDDEN, 64),
+            (POLICY_HIDDEN, 64),
+            (POLICY_HIDDEN, 4),
+        ]);
+        let network = PolicyNetwork::read(&mut bytes.as_slice()).unwrap();
+
+        for fen in [STARTPOS, PROMOTIONS] {
+            let board = Board::from_fen(fen);
+            let moves = board.gen_legal_moves();
+
+            let policy = network.policy(&board, &moves);
+            assert_eq!(policy.len(), moves.len());
+            assert!((policy.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+
+            // Moves left out as illegal get no probability, which goes to the others in
+            // proportion.
+            let legal = &moves[..moves.len() / 2];
+            let total: f32 = policy[..legal.len()].iter().sum();
+            for (p, q) in policy.iter().zip(network.policy(&board, legal)) {
+                assert!((p / total - q).abs() < 1e-5);
+            }
+        }
+
+        // The promotion head tells apart the pieces promoted to on the same squares.
+        let board = Board::from_fen(PROMOTIONS);
+        let moves: Vec<_> = board
+            .gen_legal_moves()
+            .into_iter()
+            .filter(|mov| mov.to_string().starts_with("g2g1"))
+            .collect();
+        let mut policy = network.policy(&board, &moves);
+        policy.sort_by(f32::total_cmp);
+        policy.dedup();
+
+        assert_eq!(moves.len(), 4);
+        assert_eq!(policy.len(), 4);
+    }
+
+    #[test]
+    fn assert_batched_value() {
+        let bytes = weights(&[(INPUTS, HIDDEN), (2 * HIDDEN, 1)]);
+        let network = ValueNetwork::read(&mut bytes.as_slice()).unwrap();
+
+        let positions: Vec<_> = [STARTPOS, PROMOTIONS]
+            .into_iter()
+            .map(Board::from_fen)
+            .map(|board| {
+                let accumulators = Accumulators::refresh(network.transformer(), &board);
+                (accumulators, usize::from(board.side_to_move))
+            })
+            .collect();
+        let batch: Vec<_> = positions.iter().map(|(a, side)| (a, *side)).collect();
+
+        for ((accumulators, side), value) in positions.iter().zip(network.values(&batch)) {
+            assert_eq!(network.value(accumulators, *side), value);
+        }
+    }
+
+    #[test]
+    fn assert_handcrafted_fallback() {
+        // No networks are loaded in the tests.
+        let mut pos = Chess::from_notation(PROMOTIONS);
+        let moves = pos.get_legal_moves();
+
+        assert_eq!(pos.get_policy(&moves), pos.heuristic_policy(&moves));
+        assert_eq!(pos.get_value(), pos.evaluate());
+    }
+}
@@ -0,0 +1,244 @@
+use super::{
+    board::Board,
+    moves::Move,
+    types::square::Square,
+    util::{Flag, Piece},
+};
+
+const PIECE_LETTERS: [(char, usize); 5] = [
+    ('N', Piece::KNIGHT),
+    ('B', Piece::BISHOP),
+    ('R', Piece::ROOK),
+    ('Q', Piece::QUEEN),
+    ('K', Piece::KING),
+];
+
+fn piece_from_letter(letter: char) -> Option<usize> {
+    PIECE_LETTERS
+        .iter()
+        .find(|(c, _)| *c == letter)
+        .map(|&(_, piece)| piece)
+}
+
+/// Standard algebraic notation of a legal move, e.g. `Nbd2`, `exd5`, `O-O` or `e8=Q#`. Moves are
+/// only disambiguated as far as needed, by file first and then by rank. Drops are written as in
+/// UCI, e.g. `N@f3`.
+pub fn san(board: &Board, mov: Move) -> String {
+    let mut san = match mov.flag {
+        _ if mov.dropped().is_some() => mov.to_string(),
+        Flag::KING_CASTLE => "O-O".to_owned(),
+        Flag::QUEEN_CASTLE => "O-O-O".to_owned(),
+        _ => {
+            let (_, piece) = board.piece_at(mov.from).expect("Piece on from square.");
+            let capture = mov.flag & Flag::CAPTURE != 0;
+
+            let file = char::from(b'a' + mov.from.file() as u8);
+            let rank = char::from(b'1' + mov.from.rank() as u8);
+
+            let mut san = String::new();
+            if piece == Piece::PAWN {
+                if capture {
+                    san.push(file);
+                }
+            } else {
+                let letter = PIECE_LETTERS.iter().find(|&&(_, p)| p == piece).unwrap().0;
+                san.push(letter);
+
+                let others: Vec<Move> = board
+                    .gen_legal_moves()
+                    .into_iter()
+                    .filter(|other| {
+                        other.to == mov.to
+                            && other.from != mov.from
+                            && board.piece_at(other.from).map(|(_, piece)| piece) == Some(piece)
+                    })
+                    .collect();
+
+                if !others.is_empty() {
+                    if others
+                        .iter()
+                        .all(|other| other.from.file() != mov.from.file())
+                    {
+                        san.push(file);
+                    } else if others
+                        .iter()
+                        .all(|other| other.from.rank() != mov.from.rank())
+                    {
+                        san.push(rank);
+                    } else {
+                        san.push(file);
+                        san.push(rank);
+                    }
+                }
+            }
+
+            if capture {
+                san.push('x');
+            }
+
+            san.push_str(&mov.to.to_string());
+
+            if let Some(promotion) = mov.promotion() {
+                san.push('=');
+                san.push(
+                    PIECE_LETTERS
+                        .iter()
+                        .find(|&&(_, p)| p == promotion)
+                        .unwrap()
+                        .0,
+                );
+            }
+
+            san
+        }
+    };
+
+    let mut next = *board;
+    next.make_move(mov);
+
+    if next.is_check() {
+        san.push(if next.gen_legal_moves().is_empty() {
+            '#'
+        } else {
+            '+'
+        });
+    }
+
+    san
+}
+
+/// Legal move in long algebraic notation, e.g. `e2e4`, `e1g1` or `e7e8q`.
+pub fn parse_uci(board: &Board, notation: &str) -> Option<Move> {
+    board
+        .gen_legal_moves()
+        .into_iter()
+        .find(|mov| mov.to_string() == notation)
+}
+
+/// Legal move in standard algebraic notation, e.g. `Nbd2`, `exd5`, `O-O` or `e8=Q+`. Check and
+/// annotation suffixes are ignored, and the capture sign is optional.
+pub fn parse_san(board: &Board, notation: &str) -> Option<Move> {
+    let notation = notation.trim_end_matches(['+', '#', '!', '?']);
+    let moves = board.gen_legal_moves();
+
+    let castle = match notation {
+        "O-O" | "0-0" => Some(Flag::KING_CASTLE),
+        "O-O-O" | "0-0-0" => Some(Flag::QUEEN_CASTLE),
+        _ => None,
+    };
+
+    if let Some(flag) = castle {
+        return moves.into_iter().find(|mov| mov.flag == flag);
+    }
+
+    // A pawn drop may leave out the piece, e.g. `@e4`.
+    if let Some((piece, to)) = notation.split_once('@') {
+        let piece = if piece.is_empty() { "P" } else { piece };
+        let notation = format!("{}@{}", piece, to);
+
+        return moves.into_iter().find(|mov| mov.to_string() == notation);
+    }
+
+    let mut chars: Vec<char> = notation.chars().filter(|&c| c != 'x').collect();
+
+    let piece = match chars.first() {
+        Some(&c) if c.is_ascii_uppercase() => {
+            chars.remove(0);
+            piece_from_letter(c)?
+        }
+        _ => Piece::PAWN,
+    };
+
+    let promotion_piece = match chars.iter().position(|&c| c == '=') {
+        Some(i) => {
+            let piece = piece_from_letter(*chars.get(i + 1)?)?;
+            chars.truncate(i);
+            Some(piece)
+        }
+        None if piece == Piece::PAWN && chars.last()?.is_ascii_uppercase() => {
+            piece_from_letter(chars.pop()?)
+        }
+        None => None,
+    };
+
+    if chars.len() < 2 {
+        return None;
+    }
+
+    let to = Square::parse(&chars[chars.len() - 2..].iter().collect::<String>())?;
+    let disambiguation = &chars[..chars.len() - 2];
+
+    let candidates: Vec<Move> = moves
+        .into_iter()
+        .filter(|&mov| {
+            mov.to == to
+                && mov.flag != Flag::KING_CASTLE
+                && mov.flag != Flag::QUEEN_CASTLE
+                && board.piece_at(mov.from).map(|(_, piece)| piece) == Some(piece)
+                && mov.promotion() == promotion_piece
+                && disambiguation.iter().all(|&c| {
+                    c == char::from(b'a' + mov.from.file() as u8)
+                        || c == char::from(b'1' + mov.from.rank() as u8)
+                })
+        })
+        .collect();
+
+    match candidates.as_slice() {
+        &[mov] => Some(mov),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod notation_test {
+    use super::{parse_san, parse_uci, san};
+    use crate::chess::board::{Board, STARTPOS};
+
+    #[test]
+    fn assert_parse() {
+        let board = Board::from_fen(STARTPOS);
+        assert_eq!(parse_uci(&board, "g1f3").unwrap().to_string(), "g1f3");
+        assert_eq!(parse_san(&board, "Nf3").unwrap().to_string(), "g1f3");
+        assert_eq!(parse_san(&board, "e4").unwrap().to_string(), "e2e4");
+        assert!(parse_uci(&board, "e2e5").is_none());
+        assert!(parse_san(&board, "Ke2").is_none());
+
+        let board =
+            Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+        assert_eq!(parse_san(&board, "O-O-O").unwrap().to_string(), "e1c1");
+        assert_eq!(parse_san(&board, "Qxf6").unwrap().to_string(), "f3f6");
+        assert_eq!(parse_san(&board, "dxe6").unwrap().to_string(), "d5e6");
+        assert_eq!(parse_san(&board, "Nb1").unwrap().to_string(), "c3b1");
+
+        let board = Board::from_fen("n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1");
+        assert_eq!(parse_san(&board, "gxf1=Q+").unwrap().to_string(), "g2f1q");
+        assert_eq!(parse_san(&board, "gxh1N").unwrap().to_string(), "g2h1n");
+        assert!(parse_san(&board, "e1=Q").is_none());
+    }
+
+    #[test]
+    fn assert_san() {
+        #[rustfmt::skip]
+        let cases = [
+            (STARTPOS, "g1f3", "Nf3"),
+            ("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", "e1g1", "O-O"),
+            ("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", "d5e6", "dxe6"),
+            ("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", "e5g6", "Nxg6"),
+            ("1k6/8/8/8/8/8/4K3/R6R w - - 0 1", "a1d1", "Rad1"),
+            ("7k/8/8/8/R7/8/8/R3K3 w - - 0 1", "a1a2", "R1a2"),
+            ("8/7k/8/8/Q2Q4/8/8/Q3K3 w - - 0 1", "a4d1", "Qa4d1"),
+            ("n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1", "g2h1q", "gxh1=Q"),
+            ("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1a8", "Ra8#"),
+            ("r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4", "h5f7", "Qxf7#"),
+            ("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", "a1a8", "Ra8+"),
+        ];
+
+        for (fen, uci, expected) in cases {
+            let board = Board::from_fen(fen);
+            let mov = parse_uci(&board, uci).unwrap();
+
+            assert_eq!(san(&board, mov), expected, "{}", fen);
+            assert!(parse_san(&board, expected) == Some(mov), "{}", fen);
+        }
+    }
+}
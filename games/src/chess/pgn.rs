@@ -0,0 +1,366 @@
+use std::fmt;
+
+use mentor::{Game, GameState};
+
+use super::{moves::Move, notation, variant::Variant, Chess};
+
+/// Tags every PGN game carries, in their required order.
+const SEVEN_TAG_ROSTER: [(&str, &str); 7] = [
+    ("Event", "?"),
+    ("Site", "?"),
+    ("Date", "????.??.??"),
+    ("Round", "?"),
+    ("White", "?"),
+    ("Black", "?"),
+    ("Result", "*"),
+];
+
+const RESULTS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+
+/// Game record in Portable Game Notation.
+pub struct Pgn {
+    headers: Vec<(String, String)>,
+    start: Chess,
+    moves: Vec<(Move, Option<String>)>,
+    /// Position after the last move, which is updated as moves are appended.
+    current: Chess,
+}
+
+impl Pgn {
+    pub fn new(start: Chess) -> Self {
+        let mut pgn = Pgn {
+            headers: SEVEN_TAG_ROSTER
+                .iter()
+                .map(|&(name, value)| (name.to_owned(), value.to_owned()))
+                .collect(),
+            start: start.clone(),
+            moves: Vec::new(),
+            current: start.clone(),
+        };
+
+        if start.board.variant() != Variant::Standard {
+            pgn.set_header("Variant", start.board.variant().name());
+        }
+
+        if start != Chess::default() {
+            pgn.set_header("SetUp", "1");
+            pgn.set_header("FEN", &start.notation());
+        }
+
+        pgn
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn set_header(&mut self, name: &str, value: &str) {
+        match self.headers.iter_mut().find(|(n, _)| n == name) {
+            Some((_, v)) => *v = value.to_owned(),
+            None => self.headers.push((name.to_owned(), value.to_owned())),
+        }
+    }
+
+    /// Appends a move with the root Q of the search from the perspective of the mover, which is
+    /// written as a `{eval}` comment from white's perspective.
+    pub fn push(&mut self, mov: Move, q: Option<f32>) {
+        let white = self.current.side_to_move() == 0;
+        let comment = q.map(|q| format!("{:+.2}", if white { q } else { -q }));
+
+        self.current.make_move(mov);
+        self.moves.push((mov, comment));
+    }
+
+    pub fn start(&self) -> Chess {
+        self.start.clone()
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = Move> + '_ {
+        self.moves.iter().map(|&(mov, _)| mov)
+    }
+
+    /// Position after every move, starting with the initial one.
+    pub fn positions(&self) -> Vec<Chess> {
+        let mut pos = self.start.clone();
+        let mut positions = vec![pos.clone()];
+
+        for mov in self.moves() {
+            pos.make_move(mov);
+            positions.push(pos.clone());
+        }
+
+        positions
+    }
+
+    /// Position at the end of the game.
+    pub fn position(&self) -> &Chess {
+        &self.current
+    }
+
+    /// Result of the final position if the game is over, otherwise the `Result` tag.
+    pub fn result(&self) -> &str {
+        let pos = &self.current;
+
+        match (pos.game_state(), pos.side_to_move()) {
+            (GameState::Loss, 0) | (GameState::Win, 1) => "0-1",
+            (GameState::Loss, _) | (GameState::Win, _) => "1-0",
+            (GameState::Draw, _) => "1/2-1/2",
+            _ => self.header("Result").unwrap_or("*"),
+        }
+    }
+
+    /// Reads every game of a PGN file. Comments, variations and numeric annotation glyphs are
+    /// skipped, except for comments directly after a move, which are kept.
+    pub fn read_all(text: &str) -> Result<Vec<Pgn>, String> {
+        let mut games = Vec::new();
+        let mut headers = Vec::new();
+        let mut movetext = String::new();
+
+        for line in text.lines().map(str::trim) {
+            if line.starts_with('[') && line.ends_with(']') && movetext.trim().is_empty() {
+                headers.push(parse_header(line)?);
+                continue;
+            }
+
+            if line.starts_with('%') {
+                continue;
+            }
+
+            movetext.push_str(line);
+            movetext.push('\n');
+
+            if RESULTS.iter().any(|&result| line.ends_with(result)) {
+                games.push(Self::from_parts(&headers, &movetext)?);
+
+                headers.clear();
+                movetext.clear();
+            }
+        }
+
+        if !headers.is_empty() || !movetext.trim().is_empty() {
+            games.push(Self::from_parts(&headers, &movetext)?);
+        }
+
+        Ok(games)
+    }
+
+    fn from_parts(headers: &[(String, String)], movetext: &str) -> Result<Self, String> {
+        let start = headers
+            .iter()
+            .find(|(name, _)| name == "FEN")
+            .map_or_else(Chess::default, |(_, fen)| Chess::from_notation(fen));
+
+        let mut pgn = Pgn::new(start);
+        for (name, value) in headers {
+            pgn.set_header(name, value);
+        }
+
+        for token in tokenize(movetext)? {
+            match token {
+                Token::Comment(comment) => {
+                    if let Some((_, last @ None)) = pgn.moves.last_mut() {
+                        *last = Some(comment);
+                    }
+                }
+                Token::Move(san) => {
+                    let pos = &pgn.current;
+                    let mov = pos
+                        .parse_move(&san)
+                        .ok_or_else(|| format!("Illegal move {} in {}.", san, pos.notation()))?;
+
+                    pgn.push(mov, None);
+                }
+                Token::Result(result) => pgn.set_header("Result", &result),
+            }
+        }
+
+        Ok(pgn)
+    }
+}
+
+impl fmt::Display for Pgn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let result = self.result();
+
+        for (name, value) in &self.headers {
+            let value = if name == "Result" { result } else { value };
+            writeln!(
+                f,
+                "[{} \"{}\"]",
+                name,
+                value.replace('\\', "\\\\").replace('"', "\\\"")
+            )?;
+        }
+
+        writeln!(f)?;
+
+        let mut tokens = Vec::new();
+        let mut pos = self.start.clone();
+
+        for (i, (mov, comment)) in self.moves.iter().enumerate() {
+            let number = pos.board.full_moves();
+            if pos.side_to_move() == 0 {
+                tokens.push(format!("{}.", number));
+            } else if i == 0 {
+                tokens.push(format!("{}...", number));
+            }
+
+            tokens.push(notation::san(&pos.board, *mov));
+            if let Some(comment) = comment {
+                tokens.push(format!("{{{}}}", comment));
+            }
+
+            pos.make_move(*mov);
+        }
+
+        tokens.push(result.to_owned());
+
+        // Export format keeps lines below 80 characters.
+        let mut line = String::new();
+        for token in tokens {
+            if !line.is_empty() && line.len() + token.len() >= 80 {
+                writeln!(f, "{}", line)?;
+                line.clear();
+            }
+
+            if !line.is_empty() {
+                line.push(' ');
+            }
+
+            line.push_str(&token);
+        }
+
+        writeln!(f, "{}", line)
+    }
+}
+
+fn parse_header(line: &str) -> Result<(String, String), String> {
+    let inner = &line[1..line.len() - 1];
+    let (name, value) = inner
+        .split_once(char::is_whitespace)
+        .ok_or_else(|| format!("Invalid tag {}.", line))?;
+
+    let value = value
+        .trim()
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .ok_or_else(|| format!("Invalid tag {}.", line))?;
+
+    Ok((
+        name.to_owned(),
+        value.replace("\\\"", "\"").replace("\\\\", "\\"),
+    ))
+}
+
+enum Token {
+    Move(String),
+    Comment(String),
+    Result(String),
+}
+
+fn tokenize(movetext: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = movetext.chars().peekable();
+    let mut variation = 0;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                let comment: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                if variation == 0 {
+                    tokens.push(Token::Comment(comment.trim().to_owned()));
+                }
+            }
+            ';' => {
+                chars.by_ref().take_while(|&c| c != '\n').for_each(drop);
+            }
+            '(' => variation += 1,
+            ')' => {
+                variation = usize::checked_sub(variation, 1)
+                    .ok_or_else(|| "Unbalanced variation.".to_owned())?;
+            }
+            _ if c.is_whitespace() => {}
+            _ => {
+                let mut word = String::from(c);
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || "{}();".contains(c) {
+                        break;
+                    }
+
+                    word.push(c);
+                    chars.next();
+                }
+
+                if variation > 0 || word.starts_with('$') {
+                    continue;
+                }
+
+                if RESULTS.contains(&word.as_str()) {
+                    tokens.push(Token::Result(word));
+                    continue;
+                }
+
+                // Move numbers may be glued to the move, e.g. `1.e4` or `12...Nf6`.
+                let word = word.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+                if !word.is_empty() {
+                    tokens.push(Token::Move(word.to_owned()));
+                }
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod pgn_test {
+    use super::Pgn;
+    use crate::chess::Chess;
+    use mentor::Game;
+
+    const GAME: &str = r#"[Event "Casual"]
+[Site "?"]
+[Date "????.??.??"]
+[Round "?"]
+[White "mentor"]
+[Black "mentor"]
+[Result "1-0"]
+
+1. e4 {+0.10} e5 2. Bc4 Nc6 (2... Nf6 3. d3) 3. Qh5 $2 Nf6?? 4. Qxf7# 1-0
+"#;
+
+    #[test]
+    fn assert_read_write() {
+        let games = Pgn::read_all(&format!("{}\n{}", GAME, GAME)).unwrap();
+        assert_eq!(games.len(), 2);
+
+        let pgn = &games[0];
+        assert_eq!(pgn.header("White"), Some("mentor"));
+        assert_eq!(pgn.moves().count(), 7);
+        assert_eq!(pgn.positions().len(), 8);
+        assert_eq!(pgn.result(), "1-0");
+
+        let written = pgn.to_string();
+        assert!(written.contains("1. e4 {+0.10} e5 2. Bc4 Nc6 3. Qh5 Nf6 4. Qxf7# 1-0"));
+
+        let reread = Pgn::read_all(&written).unwrap();
+        assert!(reread[0].position() == pgn.position());
+    }
+
+    #[test]
+    fn assert_push() {
+        let pos = Chess::from_notation("4k3/8/8/8/8/8/8/R3K3 b - - 0 1");
+        let mut pgn = Pgn::new(pos.clone());
+
+        let mov = pos.parse_move("Kd7").unwrap();
+        pgn.push(mov, Some(0.25));
+        assert_eq!(pgn.position().notation(), "8/3k4/8/8/8/8/8/R3K3 w - - 1 2");
+
+        let written = pgn.to_string();
+        assert!(written.contains("[FEN \"4k3/8/8/8/8/8/8/R3K3 b - - 0 1\"]"));
+        assert!(written.contains("1... Kd7 {-0.25} *"));
+    }
+}
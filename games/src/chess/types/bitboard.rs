@@ -22,6 +22,10 @@ impl Bitboard {
         Square::ALL[self.0.trailing_zeros() as usize]
     }
 
+    pub const fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+
     pub const fn swap_bytes(self) -> Bitboard {
         Bitboard(self.0.swap_bytes())
     }
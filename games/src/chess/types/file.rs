@@ -27,6 +27,6 @@ impl File {
 
 impl std::fmt::Display for File {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", char::from(97u8 - ((*self as u8) & 7)))
+        write!(f, "{}", char::from(b'a' + *self as u8))
     }
 }
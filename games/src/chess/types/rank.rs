@@ -28,6 +28,6 @@ impl Rank {
 
 impl std::fmt::Display for Rank {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", ((*self as usize) >> 3) + 1)
+        write!(f, "{}", *self as usize + 1)
     }
 }
@@ -27,6 +27,22 @@ impl Square {
         Rank::ALL[(self as usize) >> 3]
     }
 
+    pub fn parse(notation: &str) -> Option<Square> {
+        let mut chars = notation.chars();
+
+        let file = chars.next()?;
+        let rank = chars.next()?;
+        if chars.next().is_some() || !('a'..='h').contains(&file) || !('1'..='8').contains(&rank) {
+            return None;
+        }
+
+        Some(Square::ALL[(rank as usize - '1' as usize) * 8 + file as usize - 'a' as usize])
+    }
+
+    pub const fn flip(self) -> Self {
+        Square::ALL[self as usize ^ 56]
+    }
+
     pub fn shift<const SHIFT: usize>(self, side_to_move: bool) -> Self {
         if side_to_move {
             Square::ALL[self as usize - SHIFT]
@@ -38,6 +54,6 @@ impl Square {
 
 impl std::fmt::Display for Square {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{}", self.rank(), self.file())
+        write!(f, "{}{}", self.file(), self.rank())
     }
 }
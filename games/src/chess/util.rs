@@ -1,5 +1,3 @@
-use super::types::bitboard::Bitboard;
-
 #[macro_export]
 macro_rules! bitboard_loop {
     ($bitboard:expr, $square:ident, $func:expr) => {
@@ -58,6 +56,10 @@ c_enum!(Flag {
     CAPTURE: u8 = 4,
     EN_PASSANT: u8 = 5,
     PROMOTION: u8 = 8,
+    /// Drop from the pocket in Crazyhouse, with the dropped piece in the three bits above.
+    DROP: u8 = 16,
+    /// Together with `PROMOTION`, a promotion to a king in Antichess.
+    KING_PROMOTION: u8 = 32,
 });
 
 c_enum!(Castle {
@@ -69,22 +71,12 @@ c_enum!(Castle {
         Castle::WHITE_QUEEN | Castle::WHITE_KING,
         Castle::BLACK_QUEEN | Castle::BLACK_KING
     ],
-    MASK: [[Bitboard; 2]; 2] = [
-        [Bitboard(0b0000_1110), Bitboard(0b0110_0000)],
-        [Bitboard(0b0000_1110 << 56), Bitboard(0b0110_0000 << 56)]
+    /// Rights of each side, kingside first.
+    SIDES: [[u8; 2]; 2] = [
+        [Castle::WHITE_KING, Castle::WHITE_QUEEN],
+        [Castle::BLACK_KING, Castle::BLACK_QUEEN]
     ],
-    #[allow(dead_code)]
-    ROOK_MOVES: [[(usize, usize); 2]; 2] = [[(0, 3), (7, 5)], [(56, 59), (63, 61)]],
-    #[allow(dead_code)]
-    MOVES: [u8; 64] = lookup_table!(square, 64, {
-        match square {
-            0 => 7,
-            4 => 3,
-            7 => 11,
-            56 => 13,
-            60 => 12,
-            63 => 14,
-            _ => 15,
-        }
-    }),
+    /// Files the king and the rook end up on, kingside first, also in Chess960.
+    KING_FILES: [usize; 2] = [6, 2],
+    ROOK_FILES: [usize; 2] = [5, 3],
 });
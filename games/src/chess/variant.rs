@@ -0,0 +1,51 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Variant {
+    #[default]
+    Standard,
+    /// Captured pieces change sides and go to the pocket of the capturing side, from where they
+    /// may be dropped on any empty square instead of moving. Promoted pieces return to the pocket
+    /// as pawns, and pawns may not be dropped on the first or last rank.
+    Crazyhouse,
+    /// Every capture explodes the capturing piece and all pieces except pawns next to the target
+    /// square. Exploding the opponent king wins, and kings next to each other cannot give check.
+    Atomic,
+    /// Captures are mandatory and the king is an ordinary piece, to which pawns may promote as
+    /// well. A side that has lost all its pieces or cannot move wins.
+    Antichess,
+}
+
+impl Variant {
+    const ALL: [Variant; 4] = [
+        Variant::Standard,
+        Variant::Crazyhouse,
+        Variant::Atomic,
+        Variant::Antichess,
+    ];
+
+    /// Name of the variant as in the `UCI_Variant` option.
+    pub fn name(self) -> &'static str {
+        match self {
+            Variant::Standard => "chess",
+            Variant::Crazyhouse => "crazyhouse",
+            Variant::Atomic => "atomic",
+            Variant::Antichess => "antichess",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|variant| variant.name() == name)
+    }
+}
+
+static VARIANT: AtomicU8 = AtomicU8::new(0);
+
+/// Variant of new positions, which is set by the protocol option `UCI_Variant`.
+pub fn set_variant(variant: Variant) {
+    VARIANT.store(variant as u8, Ordering::Relaxed);
+}
+
+pub fn variant() -> Variant {
+    Variant::ALL[usize::from(VARIANT.load(Ordering::Relaxed))]
+}
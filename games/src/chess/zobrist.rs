@@ -0,0 +1,50 @@
+use crate::lookup_table;
+
+use super::types::square::Square;
+
+const fn random(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+const PIECES: [u64; 768] = lookup_table!(index, 768, random(index as u64));
+const CASTLE: [u64; 16] = lookup_table!(index, 16, random(768 + index as u64));
+const EN_PASSANT: [u64; 8] = lookup_table!(index, 8, random(784 + index as u64));
+const SIDE: u64 = random(792);
+const POCKETS: [u64; 170] = lookup_table!(index, 170, random(793 + index as u64));
+
+#[inline]
+pub fn piece(side: usize, piece: usize, square: Square) -> u64 {
+    PIECES[side * 384 + (piece - 2) * 64 + square as usize]
+}
+
+#[inline]
+pub fn castle(rights: u8) -> u64 {
+    CASTLE[rights as usize]
+}
+
+#[inline]
+pub fn en_passant(files: u8) -> u64 {
+    if files == 0 {
+        0
+    } else {
+        EN_PASSANT[files.trailing_zeros() as usize]
+    }
+}
+
+#[inline]
+pub fn side() -> u64 {
+    SIDE
+}
+
+/// Key of `count` pieces of a kind in the pocket of `side`, which is zero for an empty pocket.
+#[inline]
+pub fn pocket(side: usize, piece: usize, count: u8) -> u64 {
+    if count == 0 {
+        0
+    } else {
+        POCKETS[side * 85 + (piece - 2) * 17 + usize::from(count.min(16))]
+    }
+}
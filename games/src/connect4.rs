@@ -1,10 +1,14 @@
 mod board;
+mod eval;
+mod features;
 mod moves;
+mod policy;
 mod value;
 
 use std::{
     fmt::{self},
-    sync::atomic::AtomicBool,
+    path::Path,
+    sync::atomic::{AtomicBool, AtomicU8, Ordering},
 };
 
 use board::Board;
@@ -14,11 +18,80 @@ use mentor::{
 };
 use moves::Move;
 
-use crate::GameProtocol;
+use crate::{mix, registry::Registered, rollout, tune::Tunable, GameProtocol};
 
-#[derive(Clone, Copy, Default, PartialEq, Eq)]
+/// Plies after which a PopOut rollout counts as a draw, as popping can go on forever.
+const ROLLOUT_PLIES: usize = 100;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Variant {
+    #[default]
+    Standard,
+    /// Besides dropping a disc, a player may remove an own disc from the bottom row. A position
+    /// repeated for the third time is drawn, and a pop aligning four for both players wins for
+    /// the player who popped.
+    PopOut,
+}
+
+impl Variant {
+    pub fn name(self) -> &'static str {
+        match self {
+            Variant::Standard => "standard",
+            Variant::PopOut => "popout",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Variant::Standard, Variant::PopOut]
+            .into_iter()
+            .find(|variant| variant.name() == name)
+    }
+}
+
+static VARIANT: AtomicU8 = AtomicU8::new(0);
+
+/// Variant of new positions, which is set by the protocol option `Variant`.
+pub fn set_variant(variant: Variant) {
+    VARIANT.store(variant as u8, Ordering::Relaxed);
+}
+
+fn variant() -> Variant {
+    match VARIANT.load(Ordering::Relaxed) {
+        0 => Variant::Standard,
+        _ => Variant::PopOut,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Connect4 {
     board: Board,
+    variant: Variant,
+}
+
+impl Default for Connect4 {
+    fn default() -> Self {
+        Connect4::new(Board::default(), variant())
+    }
+}
+
+impl Connect4 {
+    fn new(board: Board, variant: Variant) -> Self {
+        Connect4 { board, variant }
+    }
+
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    fn moves(&self) -> Vec<Move> {
+        let mut moves: Vec<Move> = self.board.get_moves().into_iter().map(Move).collect();
+
+        if self.variant == Variant::PopOut {
+            moves.extend(self.board.get_pops().into_iter().map(Move::pop));
+        }
+
+        moves
+    }
 }
 
 impl fmt::Display for Connect4 {
@@ -30,17 +103,57 @@ impl fmt::Display for Connect4 {
 impl Game for Connect4 {
     type Move = Move;
 
+    const REPETITIONS: usize = 3;
+
+    /// Accepts a layout, optionally followed by the side to move `o` or `x` and the variant, or
+    /// the columns played from the start position, e.g. `3342p3` with `p` marking a pop. Without
+    /// a variant the one set by `set_variant` is used.
     fn from_notation(notation: &str) -> Self {
+        if notation.contains('/') {
+            let mut parts = notation.split_whitespace();
+            let mut board = Board::from_layout(parts.next().unwrap_or_default());
+            let mut variant = variant();
+
+            for part in parts {
+                match part {
+                    "o" => board.set_side_to_move(false),
+                    "x" => board.set_side_to_move(true),
+                    _ => variant = Variant::from_name(part).unwrap_or(variant),
+                }
+            }
+
+            return Connect4::new(board, variant);
+        }
+
         let mut pos = Self::default();
+        let mut pop = false;
 
-        for mov in notation.chars() {
-            let mov = mov as u16 - '0' as u16;
-            pos.make_move(mov.into());
+        for mov in notation.chars().filter(|c| !c.is_whitespace()) {
+            if mov == 'p' {
+                pop = true;
+                continue;
+            }
+
+            let col = mov as u16 - '0' as u16;
+            pos.make_move(if pop { Move::pop(col) } else { Move(col) });
+            pop = false;
         }
 
         pos
     }
 
+    /// The layout, followed by the side to move and the variant for PopOut, where the side to
+    /// move no longer follows from the number of discs.
+    fn notation(&self) -> String {
+        match self.variant {
+            Variant::Standard => self.board.layout(),
+            Variant::PopOut => {
+                let side = if self.board.side_to_move() { 'x' } else { 'o' };
+                format!("{} {} {}", self.board.layout(), side, self.variant.name())
+            }
+        }
+    }
+
     fn side_to_move(&self) -> usize {
         usize::from(self.board.side_to_move())
     }
@@ -54,60 +167,94 @@ impl Game for Connect4 {
             return GameState::Win;
         }
 
-        if self.board.mask().count_ones() == (Board::WIDTH * Board::HEIGHT) as u32 {
-            return GameState::Draw;
+        // Checking the last mover first adjudicates a pop aligning four for both players.
+        match self.variant {
+            Variant::Standard
+                if self.board.mask().count_ones() == (Board::WIDTH * Board::HEIGHT) as u32 =>
+            {
+                GameState::Draw
+            }
+            Variant::PopOut if self.moves().is_empty() => GameState::Draw,
+            _ => GameState::Ongoing,
         }
-
-        GameState::Ongoing
     }
 
     fn hash(&self) -> u64 {
-        self.board.current() + self.board.mask()
+        let key = self.board.current() + self.board.mask();
+
+        match self.variant {
+            Variant::Standard => key,
+            // After pops the key no longer tells the side to move, e.g. on an empty board.
+            Variant::PopOut => mix(key ^ u64::from(self.board.side_to_move()) << 63),
+        }
     }
 
-    fn get_value(&mut self) -> f32 {
-        let mut pos = *self;
+    /// Dropping fills the board, so only PopOut positions can repeat.
+    fn is_repetition(&self, history: &[u64]) -> bool {
+        let hash = self.hash();
 
-        let side_to_move = pos.side_to_move();
+        self.variant == Variant::PopOut
+            && history.iter().filter(|&&previous| previous == hash).count() + 1 >= Self::REPETITIONS
+    }
 
-        let mut iterations = 0;
-        while pos.game_state() == GameState::Ongoing {
-            let moves = pos.get_legal_moves();
-            let index = (rand::random::<f32>() * moves.len() as f32).floor() as usize;
+    /// The value network is trained on the standard game, so PopOut does without it.
+    fn get_value(&mut self) -> f32 {
+        if let (Variant::Standard, Some(network)) = (self.variant, value::network()) {
+            return network.value(self.board);
+        }
 
-            pos.make_move(moves[index]);
-            iterations += 1;
+        if eval::is_enabled() {
+            let parameters = eval::parameters();
+            return parameters.squash(parameters.evaluate(&eval::coefficients(&self.board)));
         }
 
+        let side_to_move = self.side_to_move();
+        let (pos, iterations) = rollout(self, ROLLOUT_PLIES);
+
         let reward = 1.0 - (iterations as f32 / 100.0);
         match pos.game_state() {
-            GameState::Draw => 0.0,
+            GameState::Ongoing | GameState::Draw => 0.0,
             _ if side_to_move == pos.side_to_move() => -reward,
             _ => reward,
         }
     }
 
-    fn get_policy(&mut self, moves: &[Self::Move]) -> Vec<f32> {
-        let mut policy = Vec::with_capacity(moves.len());
+    /// Standard positions share the passes through the value network.
+    fn get_values(positions: &mut [Self]) -> Vec<f32> {
+        let Some(network) = value::network() else {
+            return positions.iter_mut().map(|pos| pos.get_value()).collect();
+        };
 
-        for mov in moves {
-            let value: f32 = match mov.0 {
-                0 | 6 => 1.0,
-                1 | 5 => 3.0,
-                2 | 4 => 4.0,
-                3 => 7.0,
-                _ => panic!(),
-            };
+        let boards: Vec<Board> = positions
+            .iter()
+            .filter(|pos| pos.variant == Variant::Standard)
+            .map(|pos| pos.board)
+            .collect();
+        let mut batch = network.values(&boards).into_iter();
+
+        positions
+            .iter_mut()
+            .map(|pos| match pos.variant {
+                Variant::Standard => batch.next().unwrap(),
+                Variant::PopOut => pos.get_value(),
+            })
+            .collect()
+    }
 
-            policy.push(value.exp());
+    /// The policy network only knows drops, so PopOut always uses the heuristic.
+    fn get_policy(&mut self, moves: &[Self::Move]) -> Vec<f32> {
+        match (self.variant, policy::network()) {
+            (Variant::Standard, Some(network)) => network.policy(&self.board, moves),
+            _ => policy::heuristic(moves),
         }
-
-        let sum: f32 = policy.iter().sum();
-        policy.iter().map(|&x| x / sum).collect()
     }
 
     fn make_move(&mut self, mov: Self::Move) {
-        self.board.make_move(mov.into());
+        if mov.is_pop() {
+            self.board.pop(mov.col());
+        } else {
+            self.board.make_move(mov.0);
+        }
     }
 
     fn get_legal_moves(&self) -> Vec<Self::Move> {
@@ -115,10 +262,35 @@ impl Game for Connect4 {
             return Vec::new();
         }
 
-        self.board.get_moves().into_iter().map(Into::into).collect()
+        self.moves()
     }
 }
 
+impl Tunable for Connect4 {
+    fn parameters() -> Vec<f32> {
+        eval::parameters().weights.to_vec()
+    }
+
+    fn scale() -> f32 {
+        eval::parameters().scale
+    }
+
+    fn coefficients(&self) -> Vec<(usize, f32)> {
+        eval::coefficients(&self.board)
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, coefficient)| coefficient != 0.0)
+            .collect()
+    }
+}
+
+impl Registered for Connect4 {
+    type Protocol = Connect4Protocol;
+
+    const NAME: &'static str = "connect4";
+}
+
+#[derive(Default)]
 pub struct Connect4Protocol;
 
 impl GameProtocol for Connect4Protocol {
@@ -130,7 +302,43 @@ impl GameProtocol for Connect4Protocol {
 
     const DEFAULT_POS: String = String::new();
 
-    fn options(&mut self) {}
+    fn options(&mut self) {
+        println!("option name ValueNet type string default <empty>");
+        println!("option name QuantizedValueNet type check default false");
+        println!("option name PolicyNet type string default <empty>");
+        println!("option name Eval type combo default rollout var rollout var handcrafted");
+        println!("option name EvalFile type string default <empty>");
+        println!("option name Variant type combo default standard var standard var popout");
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) {
+        let path = Some(Path::new(value)).filter(|_| !value.is_empty() && value != "<empty>");
+
+        match (name, path) {
+            ("ValueNet", Some(path)) => {
+                if let Err(err) = value::load(path) {
+                    println!("info string failed to load value network: {}", err);
+                }
+            }
+            ("QuantizedValueNet", _) => value::set_quantized(value == "true"),
+            ("PolicyNet", Some(path)) => {
+                if let Err(err) = policy::load(path) {
+                    println!("info string failed to load policy network: {}", err);
+                }
+            }
+            ("EvalFile", Some(path)) => {
+                if let Err(err) = eval::load(path) {
+                    println!("info string failed to load evaluation parameters: {}", err);
+                }
+            }
+            ("Eval", _) => eval::set_enabled(value == "handcrafted"),
+            ("Variant", _) => match Variant::from_name(value) {
+                Some(variant) => set_variant(variant),
+                None => println!("info string unknown variant {}", value),
+            },
+            _ => {}
+        }
+    }
 
     fn go(
         &mut self,
@@ -144,6 +352,7 @@ impl GameProtocol for Connect4Protocol {
         let settings = SearchSettings {
             max_time: Some(25_000),
             max_nodes,
+            batch_size: crate::batch_size(),
         };
 
         let abort = AtomicBool::new(false);
@@ -152,9 +361,50 @@ impl GameProtocol for Connect4Protocol {
             s.spawn(|| {
                 let mov = search.run(Some(*pos), &settings, params, &abort, true);
                 println!("bestmove {}", mov);
+
+                abort.store(true, Ordering::Relaxed);
             });
 
             self.search_input(&abort);
         });
     }
 }
+
+#[cfg(test)]
+mod connect4_test {
+    use super::Connect4;
+    use mentor::{Game, GameState};
+
+    #[test]
+    fn assert_popout() {
+        let mut pos =
+            Connect4::from_notation("......./......./......./......./...x.../...o... o popout");
+        assert_eq!(pos.get_legal_moves().len(), 8);
+
+        pos.make_move(pos.parse_move("p3").unwrap());
+        assert_eq!(
+            pos.notation(),
+            "......./......./......./......./......./...x... x popout"
+        );
+
+        // Dropping and popping the same discs twice repeats the empty board a third time.
+        let mut pos =
+            Connect4::from_notation("......./......./......./......./......./....... o popout");
+        let mut history = Vec::new();
+        for notation in ["0", "1", "p0", "p1"].repeat(2) {
+            assert!(!pos.is_repetition(&history));
+
+            history.push(pos.hash());
+            pos.make_move(pos.parse_move(notation).unwrap());
+        }
+
+        assert!(pos.is_repetition(&history));
+        assert_eq!(pos.game_state(), GameState::Ongoing);
+
+        // The pop aligns four for both players, which wins for the player who popped.
+        let mut pos =
+            Connect4::from_notation("......./......./......./o....../xooo.../oxxx... o popout");
+        pos.make_move(pos.parse_move("p0").unwrap());
+        assert_eq!(pos.game_state(), GameState::Loss);
+    }
+}
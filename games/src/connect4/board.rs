@@ -9,12 +9,96 @@ impl Board {
     pub const WIDTH: usize = 7;
     pub const HEIGHT: usize = 6;
 
+    /// Parses rows from top to bottom separated by `/`, where `o` marks a stone of the first
+    /// player, `x` one of the second player and `.` an empty cell.
+    pub fn from_layout(layout: &str) -> Board {
+        let mut first = 0u64;
+        let mut second = 0u64;
+
+        for (i, row) in layout.split('/').take(Board::HEIGHT).enumerate() {
+            for (col, cell) in row.chars().take(Board::WIDTH).enumerate() {
+                let bit = 1u64 << ((Board::HEIGHT - 1 - i) + col * (Board::HEIGHT + 1));
+
+                match cell {
+                    'o' => first |= bit,
+                    'x' => second |= bit,
+                    _ => {}
+                }
+            }
+        }
+
+        let side_to_move = first.count_ones() > second.count_ones();
+
+        Board {
+            side_to_move,
+            current: if side_to_move { second } else { first },
+            mask: first | second,
+        }
+    }
+
+    pub fn layout(&self) -> String {
+        let (first, second) = if self.side_to_move {
+            (self.current ^ self.mask, self.current)
+        } else {
+            (self.current, self.current ^ self.mask)
+        };
+
+        let rows: Vec<String> = (0..Board::HEIGHT)
+            .rev()
+            .map(|row| {
+                (0..Board::WIDTH)
+                    .map(|col| {
+                        let bit = 1u64 << (row + col * (Board::HEIGHT + 1));
+
+                        if first & bit != 0 {
+                            'o'
+                        } else if second & bit != 0 {
+                            'x'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        rows.join("/")
+    }
+
     pub fn make_move(&mut self, col: u16) {
         self.current ^= self.mask;
         self.mask |= self.mask + Board::bottom_mask(col as usize);
         self.side_to_move ^= true;
     }
 
+    /// Removes the bottom disc of the side to move from `col`, which shifts the column down as
+    /// in PopOut.
+    pub fn pop(&mut self, col: u16) {
+        let column = Board::column_mask(col as usize);
+        let shift = |bits: u64| bits & !column | ((bits & column) >> 1) & column;
+
+        self.current = shift(self.current);
+        self.mask = shift(self.mask);
+
+        self.current ^= self.mask;
+        self.side_to_move ^= true;
+    }
+
+    /// Columns whose bottom disc belongs to the side to move.
+    pub fn get_pops(&self) -> Vec<u16> {
+        (0..Board::WIDTH)
+            .filter(|&col| self.current & Board::bottom_mask(col) != 0)
+            .map(|col| col as u16)
+            .collect()
+    }
+
+    pub fn set_side_to_move(&mut self, side_to_move: bool) {
+        if self.side_to_move != side_to_move {
+            self.current ^= self.mask;
+            self.side_to_move = side_to_move;
+        }
+    }
+
     pub fn get_moves(&self) -> Vec<u16> {
         let mut moves = Vec::new();
 
@@ -103,6 +187,10 @@ impl Board {
         (1u64 << (Board::HEIGHT - 1)) << (col * (Board::HEIGHT + 1))
     }
 
+    fn column_mask(col: usize) -> u64 {
+        ((1u64 << Board::HEIGHT) - 1) << (col * (Board::HEIGHT + 1))
+    }
+
     fn bottom_mask(col: usize) -> u64 {
         1u64 << (col * (Board::HEIGHT + 1))
     }
@@ -0,0 +1,153 @@
+use std::{
+    io,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
+
+use super::board::Board;
+use crate::tune;
+
+pub const OWN: usize = 0;
+pub const THEIRS: usize = OWN + 3;
+pub const CENTER: usize = THEIRS + 3;
+pub const NUM_PARAMS: usize = CENTER + 2;
+
+const WINDOWS: [u64; 69] = windows();
+
+static PARAMETERS: OnceLock<Parameters> = OnceLock::new();
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn parameters() -> &'static Parameters {
+    PARAMETERS.get_or_init(Parameters::default)
+}
+
+/// Replaces the default weights, which is only possible before the first evaluation.
+pub fn load(path: &Path) -> io::Result<()> {
+    let parameters = Parameters::read(path)?;
+
+    PARAMETERS.set(parameters).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "Evaluation parameters already in use.",
+        )
+    })
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub struct Parameters {
+    pub weights: [f32; NUM_PARAMS],
+    pub scale: f32,
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Parameters {
+            weights: [1.0, 5.0, 20.0, -1.0, -5.0, -25.0, 3.0, -3.0],
+            scale: 30.0,
+        }
+    }
+}
+
+impl Parameters {
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let (scale, values) = tune::read_parameters(path, NUM_PARAMS)?;
+
+        let mut weights = [0.0; NUM_PARAMS];
+        weights.copy_from_slice(&values);
+
+        Ok(Parameters { weights, scale })
+    }
+
+    pub fn evaluate(&self, coefficients: &[f32; NUM_PARAMS]) -> f32 {
+        coefficients
+            .iter()
+            .zip(self.weights.iter())
+            .map(|(c, w)| c * w)
+            .sum()
+    }
+
+    pub fn squash(&self, eval: f32) -> f32 {
+        2.0 / (1.0 + (-eval / self.scale).exp()) - 1.0
+    }
+}
+
+/// Counts the open windows of four cells holding one to three stones of a single player, and
+/// the stones in the centre column, from the perspective of the side to move.
+pub fn coefficients(board: &Board) -> [f32; NUM_PARAMS] {
+    let mut coefficients = [0.0; NUM_PARAMS];
+
+    let own = board.current();
+    let theirs = board.current() ^ board.mask();
+
+    for window in WINDOWS {
+        let (us, them) = ((own & window).count_ones(), (theirs & window).count_ones());
+
+        match (us, them) {
+            (1..=3, 0) => coefficients[OWN + us as usize - 1] += 1.0,
+            (0, 1..=3) => coefficients[THEIRS + them as usize - 1] += 1.0,
+            _ => {}
+        }
+    }
+
+    let center = 0b11_1111 << (Board::WIDTH / 2 * (Board::HEIGHT + 1));
+    coefficients[CENTER] = (own & center).count_ones() as f32;
+    coefficients[CENTER + 1] = (theirs & center).count_ones() as f32;
+
+    coefficients
+}
+
+const fn windows() -> [u64; 69] {
+    const STRIDE: usize = Board::HEIGHT + 1;
+
+    let mut windows = [0; 69];
+    let mut i = 0;
+
+    let mut col = 0;
+    while col < Board::WIDTH {
+        let mut row = 0;
+        while row < Board::HEIGHT {
+            let start = row + col * STRIDE;
+
+            // Vertical, horizontal, diagonal and anti-diagonal lines starting at this cell.
+            let directions = [
+                (1, row + 3 < Board::HEIGHT),
+                (STRIDE, col + 3 < Board::WIDTH),
+                (
+                    STRIDE + 1,
+                    col + 3 < Board::WIDTH && row + 3 < Board::HEIGHT,
+                ),
+                (STRIDE - 1, col + 3 < Board::WIDTH && row >= 3),
+            ];
+
+            let mut d = 0;
+            while d < directions.len() {
+                let (step, fits) = directions[d];
+                if fits {
+                    windows[i] = (1 << start)
+                        | (1 << (start + step))
+                        | (1 << (start + 2 * step))
+                        | (1 << (start + 3 * step));
+                    i += 1;
+                }
+
+                d += 1;
+            }
+
+            row += 1;
+        }
+
+        col += 1;
+    }
+
+    windows
+}
@@ -0,0 +1,23 @@
+use mentor::network::vector::Vector;
+
+use super::board::Board;
+
+pub const INPUTS: usize = 2 * Board::WIDTH * Board::HEIGHT;
+
+/// Encodes the stones of the side to move followed by the stones of the opponent.
+pub fn features(board: &Board) -> Vector<f32, INPUTS> {
+    let mut features = Vector([0.0; INPUTS]);
+
+    let planes = [board.current(), board.current() ^ board.mask()];
+    for (plane, stones) in planes.into_iter().enumerate() {
+        for col in 0..Board::WIDTH {
+            for row in 0..Board::HEIGHT {
+                if (stones >> (row + col * (Board::HEIGHT + 1))) & 1 != 0 {
+                    features.0[plane * INPUTS / 2 + row + col * Board::HEIGHT] = 1.0;
+                }
+            }
+        }
+    }
+
+    features
+}
@@ -1,20 +1,42 @@
-#[derive(Clone, Copy)]
+/// Column to drop a disc into, or with `POP` set a column to remove the own bottom disc from in
+/// PopOut. Written as the column like `3`, or as `p3` for a pop.
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Move(pub u16);
 
+impl Move {
+    pub const POP: u16 = 0x8;
+
+    pub fn pop(col: u16) -> Self {
+        Move(col | Self::POP)
+    }
+
+    pub fn col(self) -> u16 {
+        self.0 & !Self::POP
+    }
+
+    pub fn is_pop(self) -> bool {
+        self.0 & Self::POP != 0
+    }
+}
+
 impl std::fmt::Display for Move {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        if self.is_pop() {
+            write!(f, "p{}", self.col())
+        } else {
+            write!(f, "{}", self.0)
+        }
     }
 }
 
-impl From<u16> for Move {
-    fn from(mov: u16) -> Self {
-        Move(mov)
+impl From<u32> for Move {
+    fn from(mov: u32) -> Self {
+        Move(mov as u16)
     }
 }
 
-impl From<Move> for u16 {
+impl From<Move> for u32 {
     fn from(mov: Move) -> Self {
-        mov.0
+        u32::from(mov.0)
     }
 }
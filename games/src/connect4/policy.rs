@@ -0,0 +1,127 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+    sync::OnceLock,
+};
+
+use mentor::network::{
+    activation::{softmax, Identity, ReLu},
+    layer::{DenseLayer, Layer},
+};
+
+use super::{
+    board::Board,
+    features::{features, INPUTS},
+    moves::Move,
+};
+
+static NETWORK: OnceLock<PolicyNetwork> = OnceLock::new();
+
+pub fn network() -> Option<&'static PolicyNetwork> {
+    NETWORK.get()
+}
+
+pub fn load(path: &Path) -> io::Result<()> {
+    let network = PolicyNetwork::load(path)?;
+
+    NETWORK.set(network).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "Policy network already loaded.",
+        )
+    })
+}
+
+/// Scores every column of the board, the outputs of illegal columns are masked out.
+pub struct PolicyNetwork {
+    l1: Box<DenseLayer<f32, INPUTS, 128>>,
+    l2: Box<DenseLayer<f32, 128, { Board::WIDTH }>>,
+}
+
+impl PolicyNetwork {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::read(&mut BufReader::new(File::open(path)?))
+    }
+
+    pub fn read<R: Read>(reader: &mut R) -> io::Result<Self> {
+        Ok(PolicyNetwork {
+            l1: DenseLayer::read(reader)?,
+            l2: DenseLayer::read(reader)?,
+        })
+    }
+
+    pub fn policy(&self, board: &Board, moves: &[Move]) -> Vec<f32> {
+        let hidden = self.l1.forward::<ReLu>(features(board));
+        let logits = self.l2.forward::<Identity>(hidden);
+
+        let logits: Vec<f32> = moves.iter().map(|mov| logits.0[mov.0 as usize]).collect();
+        softmax(&logits)
+    }
+}
+
+/// Column preferences of the handcrafted fallback, favouring the centre, for drops and pops
+/// alike.
+pub fn heuristic(moves: &[Move]) -> Vec<f32> {
+    let logits: Vec<f32> = moves
+        .iter()
+        .map(|mov| match mov.col() {
+            0 | 6 => 1.0,
+            1 | 5 => 3.0,
+            2 | 4 => 4.0,
+            3 => 7.0,
+            _ => panic!(),
+        })
+        .collect();
+
+    softmax(&logits)
+}
+
+#[cfg(test)]
+mod policy_test {
+    use super::{heuristic, PolicyNetwork};
+    use crate::connect4::{board::Board, features::INPUTS, moves::Move, Connect4};
+    use mentor::Game;
+
+    #[test]
+    fn assert_masked_policy() {
+        let mut bytes = Vec::new();
+        for (i, count) in [(INPUTS + 1) * 128, (128 + 1) * Board::WIDTH]
+            .into_iter()
+            .enumerate()
+        {
+            for j in 0..count {
+                bytes.extend_from_slice(&(((j * 7 + i) as f32 * 0.13).sin() * 0.2).to_le_bytes());
+            }
+        }
+
+        let network = PolicyNetwork::read(&mut bytes.as_slice()).unwrap();
+
+        // The centre column is full, so only the other six are legal.
+        let pos = Connect4::from_notation("333333");
+        let moves = pos.get_legal_moves();
+        assert_eq!(moves.len(), 6);
+        assert!(moves.iter().all(|mov| mov.col() != 3));
+
+        let policy = network.policy(&pos.board, &moves);
+        assert_eq!(policy.len(), moves.len());
+        assert!((policy.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+
+        // The full column gets no probability, which goes to the others in proportion.
+        let columns: Vec<Move> = (0..Board::WIDTH as u16).map(Move).collect();
+        let unmasked = network.policy(&pos.board, &columns);
+        let legal = 1.0 - unmasked[3];
+        for (mov, p) in moves.iter().zip(policy) {
+            assert!((unmasked[mov.col() as usize] / legal - p).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn assert_heuristic_fallback() {
+        // No policy network is loaded in the tests.
+        let mut pos = Connect4::from_notation("333333");
+        let moves = pos.get_legal_moves();
+
+        assert_eq!(pos.get_policy(&moves), heuristic(&moves));
+    }
+}
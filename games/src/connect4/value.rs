@@ -1,17 +1,160 @@
-#![allow(dead_code)]
+use std::{
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
 
-use mentor::network::layer::DenseLayer;
+use mentor::network::{
+    activation::{ReLu, TanH},
+    layer::{DenseLayer, Layer},
+    quantized::QuantizedLayer,
+};
 
-use super::board::Board;
+use super::{
+    board::Board,
+    features::{features, INPUTS},
+};
 
-struct ValueNetwork {
-    l1: DenseLayer<f32, 84, 128>,
-    l2: DenseLayer<f32, 128, 64>,
-    l4: DenseLayer<f32, 64, 1>,
+static NETWORK: OnceLock<ValueNetwork> = OnceLock::new();
+static QUANTIZED: AtomicBool = AtomicBool::new(false);
+
+pub fn network() -> Option<&'static ValueNetwork> {
+    NETWORK.get()
+}
+
+pub fn load(path: &Path) -> io::Result<()> {
+    let network = ValueNetwork::load(path)?;
+
+    NETWORK.set(network).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "Value network already loaded.",
+        )
+    })
+}
+
+/// Evaluates with the `i16` weights instead of the trained ones, which is set by the protocol
+/// option `QuantizedValueNet`.
+pub fn set_quantized(quantized: bool) {
+    QUANTIZED.store(quantized, Ordering::Relaxed);
+}
+
+fn is_quantized() -> bool {
+    QUANTIZED.load(Ordering::Relaxed)
+}
+
+pub struct ValueNetwork {
+    l1: Box<DenseLayer<f32, INPUTS, 128>>,
+    l2: Box<DenseLayer<f32, 128, 64>>,
+    l4: Box<DenseLayer<f32, 64, 1>>,
+    quantized: QuantizedValueNetwork,
 }
 
 impl ValueNetwork {
-    pub fn value(&self, _: Board) -> f32 {
-        todo!()
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::read(&mut BufReader::new(File::open(path)?))
+    }
+
+    /// Reads the `f32` weights, which are quantized right away.
+    pub fn read<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let l1 = DenseLayer::read(reader)?;
+        let l2 = DenseLayer::read(reader)?;
+        let l4 = DenseLayer::read(reader)?;
+
+        Ok(ValueNetwork {
+            quantized: QuantizedValueNetwork {
+                l1: l1.quantize(),
+                l2: l2.quantize(),
+                l4: l4.quantize(),
+            },
+            l1,
+            l2,
+            l4,
+        })
+    }
+
+    pub fn value(&self, board: Board) -> f32 {
+        if is_quantized() {
+            return self.quantized.value(board);
+        }
+
+        let hidden = self.l1.forward::<ReLu>(features(&board));
+        let hidden = self.l2.forward::<ReLu>(hidden);
+
+        self.l4.forward::<TanH>(hidden).0[0]
+    }
+
+    /// Values of several boards, passing the whole batch through one layer after the other.
+    pub fn values(&self, boards: &[Board]) -> Vec<f32> {
+        if is_quantized() {
+            return boards
+                .iter()
+                .map(|&board| self.quantized.value(board))
+                .collect();
+        }
+
+        let inputs: Vec<_> = boards.iter().map(features).collect();
+        let hidden = self.l1.forward_batch::<ReLu>(&inputs);
+        let hidden = self.l2.forward_batch::<ReLu>(&hidden);
+
+        self.l4
+            .forward_batch::<TanH>(&hidden)
+            .into_iter()
+            .map(|output| output.0[0])
+            .collect()
+    }
+}
+
+struct QuantizedValueNetwork {
+    l1: Box<QuantizedLayer<i16, INPUTS, 128>>,
+    l2: Box<QuantizedLayer<i16, 128, 64>>,
+    l4: Box<QuantizedLayer<i16, 64, 1>>,
+}
+
+impl QuantizedValueNetwork {
+    fn value(&self, board: Board) -> f32 {
+        let hidden = self.l1.forward::<ReLu>(features(&board).quantize());
+        let hidden = self.l2.forward::<ReLu>(hidden);
+
+        self.l4.forward_f32::<TanH>(&hidden).0[0]
+    }
+}
+
+#[cfg(test)]
+mod value_test {
+    use super::ValueNetwork;
+    use crate::connect4::{board::Board, features::INPUTS};
+
+    #[test]
+    fn assert_batch_and_quantized() {
+        let mut bytes = Vec::new();
+        for (i, count) in [(INPUTS + 1) * 128, (128 + 1) * 64, 64 + 1]
+            .into_iter()
+            .enumerate()
+        {
+            for j in 0..count {
+                bytes.extend_from_slice(&(((j * 7 + i) as f32 * 0.13).sin() * 0.2).to_le_bytes());
+            }
+        }
+
+        let network = ValueNetwork::read(&mut bytes.as_slice()).unwrap();
+
+        let boards: Vec<Board> = [
+            "......./......./......./......./......./.......",
+            "......./......./......./...x.../...o.../..xo...",
+            "......./......./..o..../..xx.../.oxo.../xooxx..",
+        ]
+        .into_iter()
+        .map(Board::from_layout)
+        .collect();
+
+        for (&board, value) in boards.iter().zip(network.values(&boards)) {
+            assert_eq!(network.value(board), value);
+            assert!((network.quantized.value(board) - value).abs() < 0.05);
+        }
     }
 }
@@ -0,0 +1,235 @@
+mod board;
+mod moves;
+
+use std::fmt;
+
+use board::Board;
+use mentor::{
+    mcts::{params::SearchParameter, Search},
+    Game, GameState,
+};
+use moves::Move;
+
+use crate::{fixed_settings, mix, registry::Registered, rollout, GameProtocol};
+
+/// EinStein würfelt nicht! Each side has six numbered pieces in its corner of a 5x5 board and
+/// rolls a die before every move to find out which piece to move towards the opposite corner.
+/// Reaching that corner or capturing every opponent piece wins.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct Einstein {
+    board: Board,
+    /// Number rolled by the side to move, or 0 before the roll.
+    roll: u8,
+}
+
+impl fmt::Display for Einstein {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.board.display())
+    }
+}
+
+impl Game for Einstein {
+    type Move = Move;
+
+    /// Accepts a layout with the side to move `x` or `o` and the roll, `-` before it, e.g.
+    /// `....a/...../...../...../1.... x 3`, or rolls and moves from the start position
+    /// separated by whitespace. Moves after an illegal one are ignored.
+    fn from_notation(notation: &str) -> Self {
+        let mut parts = notation.split_whitespace();
+
+        if notation.contains('/') {
+            let layout = parts.next().unwrap_or_default();
+            let side_to_move = parts.next() == Some("o");
+            let roll = parts.next().and_then(|roll| roll.parse().ok());
+
+            return Einstein {
+                board: Board::from_layout(layout, side_to_move),
+                roll: roll.filter(|roll| (1..=6).contains(roll)).unwrap_or(0),
+            };
+        }
+
+        let mut pos = Self::default();
+
+        for notation in parts {
+            let Some(mov) = pos.parse_move(notation) else {
+                break;
+            };
+
+            pos.make_move(mov);
+        }
+
+        pos
+    }
+
+    fn notation(&self) -> String {
+        let side = if self.board.side_to_move() { 'o' } else { 'x' };
+        let roll = match self.roll {
+            0 => "-".to_owned(),
+            roll => roll.to_string(),
+        };
+
+        format!("{} {} {}", self.board.layout(), side, roll)
+    }
+
+    fn side_to_move(&self) -> usize {
+        usize::from(self.board.side_to_move())
+    }
+
+    fn game_state(&self) -> GameState {
+        if self.board.has_won(self.side_to_move() ^ 1) {
+            GameState::Loss
+        } else {
+            GameState::Ongoing
+        }
+    }
+
+    fn hash(&self) -> u64 {
+        let cells = self.board.cells();
+        let pack = |cells: &[u8]| {
+            cells
+                .iter()
+                .fold(0u64, |key, &cell| key << 4 | u64::from(cell))
+        };
+
+        let state = u64::from(self.board.side_to_move()) | u64::from(self.roll) << 1;
+        mix(pack(&cells[..16]) ^ mix(pack(&cells[16..]) << 4 | state))
+    }
+
+    fn get_value(&mut self) -> f32 {
+        let (pos, _) = rollout(self, usize::MAX);
+
+        if self.side_to_move() != pos.side_to_move() {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+
+    fn get_policy(&mut self, moves: &[Self::Move]) -> Vec<f32> {
+        vec![1.0 / moves.len() as f32; moves.len()]
+    }
+
+    fn make_move(&mut self, mov: Self::Move) {
+        if mov.is_roll() {
+            self.roll = mov.number();
+        } else {
+            self.board.make_move(mov);
+            self.roll = 0;
+        }
+    }
+
+    /// The rolls of the die before the roll, so that they can be entered like moves.
+    fn get_legal_moves(&self) -> Vec<Self::Move> {
+        if self.game_state() != GameState::Ongoing {
+            return Vec::new();
+        }
+
+        if self.is_chance() {
+            return (1..=6).map(Move::roll).collect();
+        }
+
+        self.board.get_moves(self.roll)
+    }
+
+    fn is_chance(&self) -> bool {
+        self.roll == 0
+    }
+
+    fn get_outcomes(&self) -> Vec<(Self::Move, f32)> {
+        (1..=6)
+            .map(|number| (Move::roll(number), 1.0 / 6.0))
+            .collect()
+    }
+}
+
+impl Registered for Einstein {
+    type Protocol = EinsteinProtocol;
+
+    const NAME: &'static str = "einstein";
+}
+
+#[derive(Default)]
+pub struct EinsteinProtocol;
+
+impl GameProtocol for EinsteinProtocol {
+    type Game = Einstein;
+
+    const NAME: &'static str = "uci";
+    const NEW_GAME: &'static str = "newgame";
+    const NOTATION: &'static str = "layout";
+
+    const DEFAULT_POS: String = String::new();
+
+    fn options(&mut self) {}
+
+    fn set_option(&mut self, _: &str, _: &str) {}
+
+    /// Before the roll there is nothing to search, so the engine rolls the die instead.
+    fn go(
+        &mut self,
+        pos: &mut Self::Game,
+        search: &mut Search<Self::Game>,
+        params: &SearchParameter,
+        commands: Vec<&str>,
+    ) {
+        if pos.is_chance() && pos.game_state() == GameState::Ongoing {
+            println!("bestmove {}", rand::random::<u8>() % 6 + 1);
+            return;
+        }
+
+        self.search(pos, search, params, &fixed_settings(&commands, 5_000));
+    }
+}
+
+#[cfg(test)]
+mod einstein_test {
+    use super::Einstein;
+    use mentor::{
+        mcts::{params::SearchParameter, settings::SearchSettings, Search},
+        Game, GameState,
+    };
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn assert_rolls() {
+        // Without the piece rolled, the next lower or higher piece may move.
+        let pos = Einstein::from_notation("....a/...../...../..3../1.... x 2");
+        let moves: Vec<String> = pos
+            .get_legal_moves()
+            .iter()
+            .map(|mov| mov.to_string())
+            .collect();
+        assert_eq!(moves, ["a1b1", "a1a2", "a1b2", "c2d2", "c2c3", "c2d3"]);
+
+        let mut pos = Einstein::from_notation("....a/...3./...../...../..... x 3");
+        pos.make_move(pos.parse_move("d4e5").unwrap());
+        assert_eq!(pos.game_state(), GameState::Loss);
+    }
+
+    #[test]
+    fn assert_chance_search() {
+        // Piece 1 wins on every roll but 6, which leaves only piece 6 to move and lets the last
+        // opponent piece reach its goal, so the side to move wins two thirds on average.
+        let pos = Einstein::from_notation("...../...1./...../.a.../....6 x -");
+        let mut search = Search::new(pos, 20_000);
+
+        let settings = SearchSettings {
+            max_time: None,
+            max_nodes: 20_000,
+            batch_size: 1,
+        };
+        search.run(
+            Some(pos),
+            &settings,
+            &SearchParameter::default(),
+            &AtomicBool::new(false),
+            false,
+        );
+
+        assert!(
+            (search.root_q() - 2.0 / 3.0).abs() < 0.05,
+            "{}",
+            search.root_q()
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use super::moves::Move;
+
+const SIZE: usize = 5;
+const SQUARES: usize = SIZE * SIZE;
+
+/// Corner each side has to reach, which is where the opponent starts.
+const GOALS: [usize; 2] = [SQUARES - 1, 0];
+
+/// Steps of each side: along the rank, along the file and diagonally towards its goal.
+const STEPS: [[isize; 3]; 2] = [[1, 5, 6], [-1, -5, -6]];
+
+/// Squares of the pieces numbered 1 to 6 at the start, in the corner of each side.
+const START: [[usize; 6]; 2] = [[0, 1, 2, 5, 6, 10], [24, 23, 22, 19, 18, 14]];
+
+/// Board of 5x5 squares with `a1` in the bottom left corner. Cells hold 0 when empty, the
+/// number of a piece of the first side or the number plus 6 for the second side.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Board {
+    cells: [u8; SQUARES],
+    side_to_move: bool,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        let mut cells = [0; SQUARES];
+
+        for (side, squares) in START.iter().enumerate() {
+            for (number, &square) in squares.iter().enumerate() {
+                cells[square] = Self::piece(side, number as u8 + 1);
+            }
+        }
+
+        Board {
+            cells,
+            side_to_move: false,
+        }
+    }
+}
+
+impl Board {
+    fn piece(side: usize, number: u8) -> u8 {
+        number + 6 * side as u8
+    }
+
+    fn side(cell: u8) -> Option<usize> {
+        match cell {
+            1..=6 => Some(0),
+            7..=12 => Some(1),
+            _ => None,
+        }
+    }
+
+    /// Parses ranks from top to bottom separated by `/`, where `1` to `6` are the pieces of the
+    /// first side, `a` to `f` those of the second side and `.` marks an empty square.
+    pub fn from_layout(layout: &str, side_to_move: bool) -> Self {
+        let mut cells = [0; SQUARES];
+
+        for (i, rank) in layout.split('/').take(SIZE).enumerate() {
+            for (file, c) in rank.chars().take(SIZE).enumerate() {
+                cells[(SIZE - 1 - i) * SIZE + file] = match c {
+                    '1'..='6' => c as u8 - b'0',
+                    'a'..='f' => c as u8 - b'a' + 7,
+                    _ => 0,
+                };
+            }
+        }
+
+        Board {
+            cells,
+            side_to_move,
+        }
+    }
+
+    pub fn layout(&self) -> String {
+        let ranks: Vec<String> = (0..SIZE)
+            .rev()
+            .map(|rank| {
+                (0..SIZE)
+                    .map(|file| self.cell(rank * SIZE + file))
+                    .collect()
+            })
+            .collect();
+
+        ranks.join("/")
+    }
+
+    fn cell(&self, square: usize) -> char {
+        match self.cells[square] {
+            0 => '.',
+            cell @ 1..=6 => char::from(b'0' + cell),
+            cell => char::from(b'a' + cell - 7),
+        }
+    }
+
+    pub fn side_to_move(&self) -> bool {
+        self.side_to_move
+    }
+
+    pub fn cells(&self) -> &[u8; SQUARES] {
+        &self.cells
+    }
+
+    /// Whether `side` has reached its goal or captured every opponent piece.
+    pub fn has_won(&self, side: usize) -> bool {
+        Self::side(self.cells[GOALS[side]]) == Some(side)
+            || self
+                .cells
+                .iter()
+                .all(|&cell| Self::side(cell) != Some(side ^ 1))
+    }
+
+    /// Moves after rolling `number`. The piece with that number has to move, or if it was
+    /// captured, either the next lower or the next higher piece left.
+    pub fn get_moves(&self, number: u8) -> Vec<Move> {
+        let side = usize::from(self.side_to_move);
+        let mut pieces = [None; 7];
+
+        for (square, &cell) in self.cells.iter().enumerate() {
+            if Self::side(cell) == Some(side) {
+                pieces[usize::from(cell) - 6 * side] = Some(square);
+            }
+        }
+
+        let number = usize::from(number);
+        let movable = match pieces[number] {
+            Some(square) => vec![square],
+            None => [
+                pieces[1..number].iter().rev().flatten().next(),
+                pieces[number + 1..].iter().flatten().next(),
+            ]
+            .into_iter()
+            .flatten()
+            .copied()
+            .collect(),
+        };
+
+        let mut moves = Vec::new();
+        for from in movable {
+            let (file, rank) = (from % SIZE, from / SIZE);
+
+            for (i, &step) in STEPS[side].iter().enumerate() {
+                // Steps along the rank and the diagonal change the file, the others the rank.
+                let file_ok = i == 1 || if side == 0 { file < SIZE - 1 } else { file > 0 };
+                let rank_ok = i == 0 || if side == 0 { rank < SIZE - 1 } else { rank > 0 };
+
+                if file_ok && rank_ok {
+                    moves.push(Move::new(from, from.wrapping_add_signed(step)));
+                }
+            }
+        }
+
+        moves
+    }
+
+    /// Moves a piece, capturing whichever piece is on the target square, even an own one.
+    pub fn make_move(&mut self, mov: Move) {
+        self.cells[mov.to()] = self.cells[mov.from()];
+        self.cells[mov.from()] = 0;
+        self.side_to_move ^= true;
+    }
+
+    pub fn display(&self) -> String {
+        let mut board = String::new();
+
+        for rank in (0..SIZE).rev() {
+            board.push_str(&format!("{} ", rank + 1));
+
+            for file in 0..SIZE {
+                board.push_str(&format!(" {}", self.cell(rank * SIZE + file)));
+            }
+
+            board.push('\n');
+        }
+
+        board.push_str("   a b c d e");
+        board
+    }
+}
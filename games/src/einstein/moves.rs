@@ -0,0 +1,61 @@
+/// Either a roll of the die, written as its number, or a move of a piece between two squares,
+/// written like `b2c3` with `a1` in the bottom left corner.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Move(pub u16);
+
+impl Move {
+    const ROLL: u16 = 0x8000;
+
+    pub fn new(from: usize, to: usize) -> Self {
+        Move((from as u16) << 5 | to as u16)
+    }
+
+    pub fn roll(number: u8) -> Self {
+        Move(Self::ROLL | u16::from(number))
+    }
+
+    pub fn is_roll(self) -> bool {
+        self.0 & Self::ROLL != 0
+    }
+
+    pub fn number(self) -> u8 {
+        (self.0 & 0x7) as u8
+    }
+
+    pub fn from(self) -> usize {
+        usize::from(self.0 >> 5 & 0x1F)
+    }
+
+    pub fn to(self) -> usize {
+        usize::from(self.0 & 0x1F)
+    }
+}
+
+impl std::fmt::Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_roll() {
+            return write!(f, "{}", self.number());
+        }
+
+        let square = |square: usize| {
+            format!(
+                "{}{}",
+                char::from(b'a' + (square % 5) as u8),
+                square / 5 + 1
+            )
+        };
+        write!(f, "{}{}", square(self.from()), square(self.to()))
+    }
+}
+
+impl From<u32> for Move {
+    fn from(mov: u32) -> Self {
+        Move(mov as u16)
+    }
+}
+
+impl From<Move> for u32 {
+    fn from(mov: Move) -> Self {
+        u32::from(mov.0)
+    }
+}
@@ -0,0 +1,308 @@
+mod board;
+mod gtp;
+mod moves;
+
+pub use self::gtp::GtpProtocol;
+
+use std::fmt;
+
+use board::{Board, MAX_SIZE};
+use mentor::{Game, GameState};
+use moves::Move;
+
+use crate::{mix, random_index, registry::Registered};
+
+/// Go on a board of up to 19x19 with positional superko, suicide not allowed and area scoring.
+/// The game ends after two passes in a row.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Go {
+    board: Board,
+    /// Komi in half points, so it stays comparable.
+    komi: i16,
+    passes: u8,
+    /// Hashes of the stones of every position since the start of the game.
+    history: Vec<u64>,
+}
+
+impl Default for Go {
+    fn default() -> Self {
+        Go::new(9, 7.5)
+    }
+}
+
+impl fmt::Display for Go {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.board.display())
+    }
+}
+
+impl Go {
+    pub fn new(size: usize, komi: f32) -> Self {
+        Self::from_board(Board::new(size), komi)
+    }
+
+    fn from_board(board: Board, komi: f32) -> Self {
+        let mut pos = Go {
+            board,
+            komi: (komi * 2.0).round() as i16,
+            passes: 0,
+            history: Vec::new(),
+        };
+
+        pos.history.push(pos.board.hash());
+        pos
+    }
+
+    pub fn size(&self) -> usize {
+        self.board.size()
+    }
+
+    pub fn komi(&self) -> f32 {
+        f32::from(self.komi) / 2.0
+    }
+
+    pub fn set_komi(&mut self, komi: f32) {
+        self.komi = (komi * 2.0).round() as i16;
+    }
+
+    /// Lets `side` move next without a pass, as GTP allows either colour to play.
+    pub fn set_side_to_move(&mut self, side: usize) {
+        self.board.set_side_to_move(side == 1);
+    }
+
+    /// Area score of black minus white including komi.
+    pub fn score(&self) -> f32 {
+        self.board.score() as f32 - self.komi()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.passes >= 2
+    }
+}
+
+impl Game for Go {
+    type Move = Move;
+
+    /// Accepts a layout with the side to move and optionally the komi, e.g. `.../... x 7.5`, or
+    /// moves on the 9x9 board separated by whitespace. Moves after an illegal one are ignored.
+    fn from_notation(notation: &str) -> Self {
+        let mut parts = notation.split_whitespace();
+
+        if notation.contains('/') {
+            let layout = parts.next().unwrap_or_default();
+            let side_to_move = parts.next() == Some("o");
+            let komi = parts.next().and_then(|komi| komi.parse().ok());
+
+            return Self::from_board(
+                Board::from_layout(layout, side_to_move),
+                komi.unwrap_or(7.5),
+            );
+        }
+
+        let mut pos = Self::default();
+
+        for notation in parts {
+            let Some(mov) = pos.parse_move(notation) else {
+                break;
+            };
+
+            pos.make_move(mov);
+        }
+
+        pos
+    }
+
+    fn notation(&self) -> String {
+        let side = if self.board.side_to_move() { 'o' } else { 'x' };
+
+        format!("{} {} {}", self.board.layout(), side, self.komi())
+    }
+
+    fn side_to_move(&self) -> usize {
+        usize::from(self.board.side_to_move())
+    }
+
+    fn game_state(&self) -> GameState {
+        if !self.is_over() {
+            return GameState::Ongoing;
+        }
+
+        let score = if self.board.side_to_move() {
+            -self.score()
+        } else {
+            self.score()
+        };
+
+        match score.total_cmp(&0.0) {
+            std::cmp::Ordering::Greater => GameState::Win,
+            std::cmp::Ordering::Less => GameState::Loss,
+            std::cmp::Ordering::Equal => GameState::Draw,
+        }
+    }
+
+    fn hash(&self) -> u64 {
+        let side = u64::from(self.board.side_to_move());
+        let passes = u64::from(self.passes.min(2));
+
+        self.board.hash() ^ mix(side | passes << 1)
+    }
+
+    /// Plays random moves until both sides pass, never filling an own eye and only checking
+    /// simple ko, and scores the final position.
+    fn get_value(&mut self) -> f32 {
+        let side_to_move = self.board.side_to_move();
+        let mut board = self.board;
+        let mut passes = self.passes;
+
+        for _ in 0..3 * MAX_SIZE * MAX_SIZE {
+            if passes >= 2 {
+                break;
+            }
+
+            let mut moves: Vec<Move> = board.points().collect();
+            let mov = loop {
+                if moves.is_empty() {
+                    break Move::PASS;
+                }
+
+                let mov = moves.swap_remove(random_index(moves.len()));
+
+                if board.is_legal(mov) && !board.is_eye(mov) {
+                    break mov;
+                }
+            };
+
+            if mov == Move::PASS {
+                board.pass();
+                passes += 1;
+            } else {
+                board.make_move(mov);
+                passes = 0;
+            }
+        }
+
+        let score = board.score() as f32 - self.komi();
+        let score = if side_to_move { -score } else { score };
+
+        match score.total_cmp(&0.0) {
+            std::cmp::Ordering::Greater => 1.0,
+            std::cmp::Ordering::Less => -1.0,
+            std::cmp::Ordering::Equal => 0.0,
+        }
+    }
+
+    fn get_policy(&mut self, moves: &[Self::Move]) -> Vec<f32> {
+        vec![1.0 / moves.len() as f32; moves.len()]
+    }
+
+    fn make_move(&mut self, mov: Self::Move) {
+        if mov == Move::PASS {
+            self.board.pass();
+            self.passes += 1;
+        } else {
+            self.board.make_move(mov);
+            self.passes = 0;
+        }
+
+        self.history.push(self.board.hash());
+    }
+
+    /// Moves are matched regardless of case, as GTP vertices are case insensitive.
+    fn parse_move(&self, notation: &str) -> Option<Self::Move> {
+        self.get_legal_moves()
+            .into_iter()
+            .find(|mov| mov.to_string().eq_ignore_ascii_case(notation))
+    }
+
+    fn get_legal_moves(&self) -> Vec<Self::Move> {
+        if self.is_over() {
+            return Vec::new();
+        }
+
+        let mut moves: Vec<Move> = self
+            .board
+            .points()
+            .filter(|&mov| {
+                if !self.board.is_legal(mov) {
+                    return false;
+                }
+
+                let mut board = self.board;
+                board.make_move(mov);
+
+                !self.history.contains(&board.hash())
+            })
+            .collect();
+
+        moves.push(Move::PASS);
+        moves
+    }
+}
+
+impl Registered for Go {
+    type Protocol = GtpProtocol;
+
+    const NAME: &'static str = "go";
+}
+
+#[cfg(test)]
+mod go_test {
+    use super::Go;
+    use mentor::{Game, GameState};
+
+    #[test]
+    fn assert_captures_and_suicide() {
+        // Black captures the white stone on C3 by filling its last liberty.
+        let mut pos = Go::from_notation("...../..x../.xo../..x../..... x");
+        pos.make_move(pos.parse_move("d3").unwrap());
+        assert_eq!(pos.notation(), "...../..x../.x.x./..x../..... o 7.5");
+
+        // White may not play into the eye, but black may fill it.
+        assert!(pos.parse_move("c3").is_none());
+        pos.make_move(pos.parse_move("pass").unwrap());
+        assert!(pos.parse_move("c3").is_some());
+
+        // Filling the last liberty of two white groups is no suicide as it captures them.
+        let mut pos = Go::from_notation(".ox../ox.../x..../...../..... x");
+        pos.make_move(pos.parse_move("A5").unwrap());
+        assert_eq!(pos.notation(), "x.x../.x.../x..../...../..... o 7.5");
+    }
+
+    #[test]
+    fn assert_superko() {
+        // White captures on C3 and black may not retake on D3 at once.
+        let mut pos = Go::from_notation("...../..xo./.x.xo/..xo./..... o");
+        pos.make_move(pos.parse_move("c3").unwrap());
+        assert!(pos.parse_move("d3").is_none());
+
+        // After moves elsewhere the ko may be retaken.
+        pos.make_move(pos.parse_move("a1").unwrap());
+        pos.make_move(pos.parse_move("e1").unwrap());
+        assert!(pos.parse_move("d3").is_some());
+
+        // White captures two stones with C3, and retaking one with C2 would return to the
+        // position before B2. Simple ko allows it, as more than one stone was captured.
+        let mut pos = Go::new(3, 0.5);
+        for notation in ["b3", "b1", "c2", "b2", "c1", "c3"] {
+            pos.make_move(pos.parse_move(notation).unwrap());
+        }
+
+        let mov = pos.board.points().find(|mov| mov.to_string() == "C2");
+        assert!(pos.board.is_legal(mov.unwrap()));
+        assert!(pos.parse_move("c2").is_none());
+    }
+
+    #[test]
+    fn assert_scoring() {
+        let pos = Go::new(5, 0.5);
+        assert_eq!(pos.get_legal_moves().len(), 26);
+
+        // Black owns the left two columns and white the right two.
+        let mut pos = Go::from_notation(".x.o./.x.o./.x.o./.x.o./.x.o. x 0.5");
+        pos.make_move(pos.parse_move("pass").unwrap());
+        pos.make_move(pos.parse_move("pass").unwrap());
+        assert_eq!(pos.score(), -0.5);
+        assert_eq!(pos.game_state(), GameState::Loss);
+        assert!(pos.get_legal_moves().is_empty());
+    }
+}
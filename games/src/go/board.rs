@@ -0,0 +1,376 @@
+use super::moves::{column, Move};
+
+pub const MAX_SIZE: usize = 19;
+
+/// Points are stored row by row from the bottom with a border of edge points around the board,
+/// so neighbours never need bounds checks.
+const STRIDE: usize = MAX_SIZE + 2;
+const POINTS: usize = STRIDE * STRIDE;
+const WORDS: usize = POINTS.div_ceil(64);
+
+const EMPTY: u8 = 0;
+const BLACK: u8 = 1;
+const WHITE: u8 = 2;
+const EDGE: u8 = 3;
+
+/// The corner of the border is never played, so it marks the absence of a ko point.
+const NO_KO: u16 = 0;
+
+static KEYS: [[u64; POINTS]; 2] = keys();
+
+/// Zobrist keys for black and white stones, generated with splitmix64.
+const fn keys() -> [[u64; POINTS]; 2] {
+    let mut keys = [[0; POINTS]; 2];
+    let mut seed: u64 = 0x5EED_0F60;
+
+    let mut i = 0;
+    while i < 2 * POINTS {
+        seed = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        keys[i / POINTS][i % POINTS] = z ^ (z >> 31);
+
+        i += 1;
+    }
+
+    keys
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Board {
+    cells: [u8; POINTS],
+    size: u8,
+    side_to_move: bool,
+    ko: u16,
+    hash: u64,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board::new(9)
+    }
+}
+
+impl Board {
+    pub fn new(size: usize) -> Self {
+        let size = size.clamp(1, MAX_SIZE);
+        let mut cells = [EDGE; POINTS];
+
+        for row in 0..size {
+            for col in 0..size {
+                cells[Self::index(col, row)] = EMPTY;
+            }
+        }
+
+        Board {
+            cells,
+            size: size as u8,
+            side_to_move: false,
+            ko: NO_KO,
+            hash: 0,
+        }
+    }
+
+    const fn index(col: usize, row: usize) -> usize {
+        (row + 1) * STRIDE + col + 1
+    }
+
+    fn point(mov: Move) -> usize {
+        Self::index(mov.col(), mov.row())
+    }
+
+    fn neighbours(point: usize) -> [usize; 4] {
+        [point - 1, point + 1, point - STRIDE, point + STRIDE]
+    }
+
+    /// Parses rows from top to bottom separated by `/`, where `x` marks a black stone, `o` a
+    /// white stone and `.` an empty point. The number of rows gives the size of the board.
+    pub fn from_layout(layout: &str, side_to_move: bool) -> Self {
+        let rows: Vec<&str> = layout.split('/').collect();
+        let mut board = Board::new(rows.len());
+        let size = board.size();
+
+        for (i, cells) in rows.iter().take(size).enumerate() {
+            for (col, cell) in cells.chars().take(size).enumerate() {
+                let point = Self::index(col, size - 1 - i);
+
+                match cell {
+                    'x' => board.place(point, BLACK),
+                    'o' => board.place(point, WHITE),
+                    _ => {}
+                }
+            }
+        }
+
+        board.side_to_move = side_to_move;
+        board
+    }
+
+    pub fn layout(&self) -> String {
+        let rows: Vec<String> = (0..self.size())
+            .rev()
+            .map(|row| (0..self.size()).map(|col| self.cell(col, row)).collect())
+            .collect();
+
+        rows.join("/")
+    }
+
+    pub fn cell(&self, col: usize, row: usize) -> char {
+        match self.cells[Self::index(col, row)] {
+            BLACK => 'x',
+            WHITE => 'o',
+            _ => '.',
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        usize::from(self.size)
+    }
+
+    pub fn side_to_move(&self) -> bool {
+        self.side_to_move
+    }
+
+    pub fn set_side_to_move(&mut self, side_to_move: bool) {
+        if self.side_to_move != side_to_move {
+            self.side_to_move = side_to_move;
+            self.ko = NO_KO;
+        }
+    }
+
+    /// Hash of the stones only, as positional superko ignores the side to move.
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    fn colour(&self) -> u8 {
+        if self.side_to_move {
+            WHITE
+        } else {
+            BLACK
+        }
+    }
+
+    fn place(&mut self, point: usize, colour: u8) {
+        self.cells[point] = colour;
+        self.hash ^= KEYS[usize::from(colour - 1)][point];
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Move> {
+        let size = self.size();
+
+        (0..size).flat_map(move |row| (0..size).map(move |col| Move::new(col, row)))
+    }
+
+    /// Visits the group at `point`, calling `stone` for each of its stones and `neighbour` for
+    /// each adjacent point of another colour, until `neighbour` returns true.
+    fn visit_group(
+        &self,
+        point: usize,
+        mut stone: impl FnMut(usize),
+        mut neighbour: impl FnMut(usize) -> bool,
+    ) -> bool {
+        let colour = self.cells[point];
+        let mut visited = [0u64; WORDS];
+        let mut stack = [0u16; MAX_SIZE * MAX_SIZE];
+        let mut len = 1;
+
+        stack[0] = point as u16;
+        visited[point / 64] |= 1 << (point % 64);
+
+        while len > 0 {
+            len -= 1;
+            let point = usize::from(stack[len]);
+            stone(point);
+
+            for next in Self::neighbours(point) {
+                if self.cells[next] != colour {
+                    if neighbour(next) {
+                        return true;
+                    }
+                } else if visited[next / 64] & (1 << (next % 64)) == 0 {
+                    visited[next / 64] |= 1 << (next % 64);
+                    stack[len] = next as u16;
+                    len += 1;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Whether the group at `point` has a liberty other than `except`.
+    fn has_liberty(&self, point: usize, except: usize) -> bool {
+        self.visit_group(
+            point,
+            |_| {},
+            |next| self.cells[next] == EMPTY && next != except,
+        )
+    }
+
+    /// Whether the side to move may place a stone on `mov`, which rules out occupied points,
+    /// suicide and retaking a ko immediately.
+    pub fn is_legal(&self, mov: Move) -> bool {
+        let point = Self::point(mov);
+        if self.cells[point] != EMPTY || point == usize::from(self.ko) {
+            return false;
+        }
+
+        let colour = self.colour();
+
+        Self::neighbours(point).into_iter().any(|next| {
+            match self.cells[next] {
+                EMPTY => true,
+                EDGE => false,
+                // Connecting keeps a liberty, and a neighbour without another liberty is captured.
+                cell if cell == colour => self.has_liberty(next, point),
+                _ => !self.has_liberty(next, point),
+            }
+        })
+    }
+
+    /// Places a stone for the side to move and removes the opponent groups without liberties.
+    /// The move has to be legal.
+    pub fn make_move(&mut self, mov: Move) {
+        let point = Self::point(mov);
+        let colour = self.colour();
+        self.place(point, colour);
+
+        let mut captured = Vec::new();
+        for next in Self::neighbours(point) {
+            let cell = self.cells[next];
+
+            if cell != EMPTY && cell != EDGE && cell != colour && !self.has_liberty(next, 0) {
+                let mut group = Vec::new();
+                self.visit_group(next, |stone| group.push(stone), |_| false);
+
+                for &stone in &group {
+                    self.cells[stone] = EMPTY;
+                    self.hash ^= KEYS[usize::from(cell - 1)][stone];
+                }
+
+                captured.extend(group);
+            }
+        }
+
+        // A lone stone which captured a single stone and has no other liberty could be captured
+        // right back, which is a ko.
+        self.ko = match captured[..] {
+            [stone]
+                if Self::neighbours(point).iter().all(|&next| {
+                    next == stone || (self.cells[next] != colour && self.cells[next] != EMPTY)
+                }) =>
+            {
+                stone as u16
+            }
+            _ => NO_KO,
+        };
+
+        self.side_to_move ^= true;
+    }
+
+    pub fn pass(&mut self) {
+        self.side_to_move ^= true;
+        self.ko = NO_KO;
+    }
+
+    /// Whether `mov` is an eye of the side to move: every neighbour is an own stone and at most
+    /// one diagonal, or none at the edge, belongs to the opponent.
+    pub fn is_eye(&self, mov: Move) -> bool {
+        let point = Self::point(mov);
+        let colour = self.colour();
+
+        if Self::neighbours(point)
+            .iter()
+            .any(|&next| self.cells[next] != colour && self.cells[next] != EDGE)
+        {
+            return false;
+        }
+
+        let diagonals = [
+            point - STRIDE - 1,
+            point - STRIDE + 1,
+            point + STRIDE - 1,
+            point + STRIDE + 1,
+        ];
+        let opponent = diagonals
+            .iter()
+            .filter(|&&next| self.cells[next] == BLACK + WHITE - colour)
+            .count();
+        let edge = diagonals.iter().any(|&next| self.cells[next] == EDGE);
+
+        opponent + usize::from(edge) < 2
+    }
+
+    /// Area score of black minus white: stones on the board plus empty regions which reach only
+    /// stones of one colour.
+    pub fn score(&self) -> i32 {
+        let mut score = 0;
+        let mut visited = [false; POINTS];
+
+        for mov in self.points() {
+            let point = Self::point(mov);
+
+            match self.cells[point] {
+                BLACK => score += 1,
+                WHITE => score -= 1,
+                _ if !visited[point] => {
+                    let (mut region, mut reaches) = (0, 0);
+                    let mut stack = vec![point];
+                    visited[point] = true;
+
+                    while let Some(point) = stack.pop() {
+                        region += 1;
+
+                        for next in Self::neighbours(point) {
+                            match self.cells[next] {
+                                EMPTY if !visited[next] => {
+                                    visited[next] = true;
+                                    stack.push(next);
+                                }
+                                BLACK | WHITE => reaches |= self.cells[next],
+                                _ => {}
+                            }
+                        }
+                    }
+
+                    match reaches {
+                        BLACK => score += region,
+                        WHITE => score -= region,
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        score
+    }
+
+    pub fn display(&self) -> String {
+        let size = self.size();
+        let columns: String = (0..size).map(|col| format!(" {}", column(col))).collect();
+        let mut board = format!("  {}", columns);
+
+        for row in (0..size).rev() {
+            board.push_str(&format!("\n{:>2}", row + 1));
+
+            for col in 0..size {
+                let cell = match self.cell(col, row) {
+                    'x' => 'X',
+                    'o' => 'O',
+                    _ => '.',
+                };
+
+                board.push_str(&format!(" {}", cell));
+            }
+
+            board.push_str(&format!(" {}", row + 1));
+        }
+
+        board.push_str(&format!("\n  {}", columns));
+        board
+    }
+}
@@ -0,0 +1,185 @@
+use std::sync::atomic::AtomicBool;
+
+use mentor::{
+    mcts::{params::SearchParameter, Search},
+    Game,
+};
+
+use super::{board::MAX_SIZE, Go};
+use crate::{fixed_settings, handle_input, GameProtocol};
+
+const COMMANDS: &[&str] = &[
+    "boardsize",
+    "clear_board",
+    "final_score",
+    "genmove",
+    "known_command",
+    "komi",
+    "list_commands",
+    "name",
+    "play",
+    "protocol_version",
+    "quit",
+    "showboard",
+    "version",
+];
+
+/// Go Text Protocol, version 2. Commands may be prefixed with a numeric id, which is repeated
+/// in the response.
+#[derive(Default)]
+pub struct GtpProtocol;
+
+impl GtpProtocol {
+    fn execute(
+        &mut self,
+        pos: &mut Go,
+        search: &mut Search<Go>,
+        params: &SearchParameter,
+        command: &str,
+        args: &[&str],
+    ) -> Result<String, String> {
+        match command {
+            "protocol_version" => Ok("2".to_owned()),
+            "name" => Ok("mentor".to_owned()),
+            "version" => Ok(env!("CARGO_PKG_VERSION").to_owned()),
+            "known_command" => Ok(args
+                .first()
+                .is_some_and(|name| COMMANDS.contains(name))
+                .to_string()),
+            "list_commands" => Ok(COMMANDS.join("\n")),
+            "quit" => Ok(String::new()),
+            "boardsize" => {
+                let size = args
+                    .first()
+                    .and_then(|size| size.parse::<usize>().ok())
+                    .ok_or("boardsize not an integer")?;
+
+                if !(2..=MAX_SIZE).contains(&size) {
+                    return Err("unacceptable size".to_owned());
+                }
+
+                *pos = Go::new(size, pos.komi());
+                Ok(String::new())
+            }
+            "clear_board" => {
+                *pos = Go::new(pos.size(), pos.komi());
+                Ok(String::new())
+            }
+            "komi" => {
+                let komi = args
+                    .first()
+                    .and_then(|komi| komi.parse::<f32>().ok())
+                    .ok_or("komi not a float")?;
+
+                pos.set_komi(komi);
+                Ok(String::new())
+            }
+            "play" => {
+                let [colour, vertex] = args else {
+                    return Err("invalid color or coordinate".to_owned());
+                };
+
+                let mut new_pos = pos.clone();
+                new_pos.set_side_to_move(side(colour).ok_or("invalid color")?);
+
+                let mov = new_pos.parse_move(vertex).ok_or("illegal move")?;
+                new_pos.make_move(mov);
+
+                *pos = new_pos;
+                Ok(String::new())
+            }
+            "genmove" => {
+                let colour = args.first().ok_or("invalid color")?;
+                pos.set_side_to_move(side(colour).ok_or("invalid color")?);
+
+                if pos.is_over() {
+                    return Ok("pass".to_owned());
+                }
+
+                let settings = fixed_settings(&[], 5_000);
+                let mov = search.run(
+                    Some(pos.clone()),
+                    &settings,
+                    params,
+                    &AtomicBool::new(false),
+                    false,
+                );
+                pos.make_move(mov);
+
+                Ok(mov.to_string())
+            }
+            "showboard" => Ok(format!("\n{}", pos)),
+            "final_score" => {
+                let score = pos.score();
+
+                Ok(match score.total_cmp(&0.0) {
+                    std::cmp::Ordering::Greater => format!("B+{}", score),
+                    std::cmp::Ordering::Less => format!("W+{}", -score),
+                    std::cmp::Ordering::Equal => "0".to_owned(),
+                })
+            }
+            _ => Err("unknown command".to_owned()),
+        }
+    }
+}
+
+/// Side of a GTP colour, which may be written out or abbreviated in any case.
+fn side(colour: &str) -> Option<usize> {
+    match colour.to_ascii_lowercase().as_str() {
+        "b" | "black" => Some(0),
+        "w" | "white" => Some(1),
+        _ => None,
+    }
+}
+
+impl GameProtocol for GtpProtocol {
+    type Game = Go;
+
+    const NAME: &'static str = "gtp";
+    const NEW_GAME: &'static str = "clear_board";
+    const NOTATION: &'static str = "layout";
+
+    const DEFAULT_POS: String = String::new();
+
+    /// Answers every command with `=` or `?` followed by an empty line, as GTP expects instead of
+    /// the handshake of the other protocols.
+    fn run(&mut self) {
+        let mut pos = Go::default();
+        let params = SearchParameter::default();
+        let mut search = Search::new(pos.clone(), 50_000);
+
+        handle_input(&AtomicBool::new(false), |_, commands| {
+            let (id, commands) = match commands.first().map(|id| id.parse::<u32>()) {
+                Some(Ok(id)) => (id.to_string(), &commands[1..]),
+                _ => (String::new(), &commands[..]),
+            };
+
+            let Some((&command, args)) = commands.split_first() else {
+                return;
+            };
+
+            match self.execute(&mut pos, &mut search, &params, command, args) {
+                Ok(response) => println!("={} {}\n", id, response),
+                Err(err) => println!("?{} {}\n", id, err),
+            }
+
+            if command == "quit" {
+                std::process::exit(0);
+            }
+        });
+    }
+
+    fn options(&mut self) {}
+
+    fn set_option(&mut self, _: &str, _: &str) {}
+
+    fn go(
+        &mut self,
+        pos: &mut Self::Game,
+        search: &mut Search<Self::Game>,
+        params: &SearchParameter,
+        commands: Vec<&str>,
+    ) {
+        self.search(pos, search, params, &fixed_settings(&commands, 5_000));
+    }
+}
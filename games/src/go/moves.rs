@@ -0,0 +1,47 @@
+/// Intersection given by column and row from the bottom left corner, written like `D4` with the
+/// column letters skipping `I` as in GTP, or a pass.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Move(pub u16);
+
+impl Move {
+    pub const PASS: Move = Move(u16::MAX);
+
+    pub fn new(col: usize, row: usize) -> Self {
+        Move((col as u16) << 8 | row as u16)
+    }
+
+    pub fn col(self) -> usize {
+        usize::from(self.0 >> 8)
+    }
+
+    pub fn row(self) -> usize {
+        usize::from(self.0 & 0xFF)
+    }
+}
+
+/// Column letter as in GTP, which leaves out `I`.
+pub fn column(col: usize) -> char {
+    char::from(b'A' + col as u8 + u8::from(col >= 8))
+}
+
+impl std::fmt::Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if *self == Move::PASS {
+            return write!(f, "pass");
+        }
+
+        write!(f, "{}{}", column(self.col()), self.row() + 1)
+    }
+}
+
+impl From<u32> for Move {
+    fn from(mov: u32) -> Self {
+        Move(mov as u16)
+    }
+}
+
+impl From<Move> for u32 {
+    fn from(mov: Move) -> Self {
+        u32::from(mov.0)
+    }
+}
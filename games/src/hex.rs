@@ -0,0 +1,190 @@
+mod board;
+mod moves;
+
+use std::fmt;
+
+use board::Board;
+use mentor::{
+    mcts::{params::SearchParameter, Search},
+    Game, GameState,
+};
+use moves::Move;
+
+use crate::{fixed_settings, mix, random_index, registry::Registered, GameProtocol};
+
+/// Hex on an `N` by `N` board. A full board always has a winner, so there are no draws.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct Hex<const N: usize> {
+    board: Board<N>,
+}
+
+pub type Hex11 = Hex<11>;
+
+impl<const N: usize> fmt::Display for Hex<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.board.display())
+    }
+}
+
+impl<const N: usize> Game for Hex<N> {
+    type Move = Move;
+
+    /// Accepts a layout as written by `notation`, or moves separated by whitespace, e.g.
+    /// `f6 e7 d5`. Moves after an illegal one are ignored.
+    fn from_notation(notation: &str) -> Self {
+        if notation.contains('/') {
+            return Hex {
+                board: Board::from_layout(notation),
+            };
+        }
+
+        let mut pos = Self::default();
+
+        for notation in notation.split_whitespace() {
+            let Some(mov) = pos.parse_move(notation) else {
+                break;
+            };
+
+            pos.make_move(mov);
+        }
+
+        pos
+    }
+
+    fn notation(&self) -> String {
+        self.board.layout()
+    }
+
+    fn side_to_move(&self) -> usize {
+        usize::from(self.board.side_to_move())
+    }
+
+    fn game_state(&self) -> GameState {
+        if self.board.connected(self.side_to_move() ^ 1) {
+            GameState::Loss
+        } else {
+            GameState::Ongoing
+        }
+    }
+
+    fn hash(&self) -> u64 {
+        self.board
+            .stones(0)
+            .0
+            .iter()
+            .chain(self.board.stones(1).0.iter())
+            .fold(0, |hash, &word| mix(hash ^ word))
+    }
+
+    /// Fills the board randomly, which decides the game without checking for a winner after
+    /// every move.
+    fn get_value(&mut self) -> f32 {
+        let mut pos = *self;
+        let side = self.side_to_move();
+
+        if pos.game_state() == GameState::Loss {
+            return -1.0;
+        }
+
+        let mut moves = pos.get_legal_moves();
+        while !moves.is_empty() {
+            pos.board
+                .make_move(moves.swap_remove(random_index(moves.len())));
+        }
+
+        if pos.board.connected(side) {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+
+    fn get_policy(&mut self, moves: &[Self::Move]) -> Vec<f32> {
+        vec![1.0 / moves.len() as f32; moves.len()]
+    }
+
+    fn make_move(&mut self, mov: Self::Move) {
+        self.board.make_move(mov);
+    }
+
+    fn get_legal_moves(&self) -> Vec<Self::Move> {
+        if self.game_state() != GameState::Ongoing {
+            return Vec::new();
+        }
+
+        self.board.get_moves()
+    }
+}
+
+impl Registered for Hex11 {
+    type Protocol = HexProtocol<11>;
+
+    const NAME: &'static str = "hex";
+}
+
+#[derive(Default)]
+pub struct HexProtocol<const N: usize>;
+
+impl<const N: usize> GameProtocol for HexProtocol<N> {
+    type Game = Hex<N>;
+
+    const NAME: &'static str = "uci";
+    const NEW_GAME: &'static str = "newgame";
+    const NOTATION: &'static str = "layout";
+
+    const DEFAULT_POS: String = String::new();
+
+    fn options(&mut self) {}
+
+    fn set_option(&mut self, _: &str, _: &str) {}
+
+    fn go(
+        &mut self,
+        pos: &mut Self::Game,
+        search: &mut Search<Self::Game>,
+        params: &SearchParameter,
+        commands: Vec<&str>,
+    ) {
+        self.search(pos, search, params, &fixed_settings(&commands, 5_000));
+    }
+}
+
+#[cfg(test)]
+mod hex_test {
+    use super::Hex;
+    use crate::random_index;
+    use mentor::{Game, GameState};
+
+    #[test]
+    fn assert_connection() {
+        // Black connects top and bottom along the second column, white is one cell short.
+        let pos = Hex::<5>::from_notation("b1 a1 b2 a2 b3 a3 b4 a4 b5");
+        assert_eq!(pos.game_state(), GameState::Loss);
+        assert!(pos.get_legal_moves().is_empty());
+
+        // A chain along the short diagonal bends, which only the hex neighbours connect.
+        let pos = Hex::<5>::from_notation("e1 a1 d2 a2 c3 a3 b4 a4 a5");
+        assert_eq!(pos.game_state(), GameState::Loss);
+
+        // a2 and b3 do not touch, so white has no connection yet.
+        let pos = Hex::<3>::from_notation("b1 a1 b2 a2 c1 b3 c2");
+        assert_eq!(pos.side_to_move(), 1);
+        assert_eq!(pos.game_state(), GameState::Ongoing);
+
+        let pos = Hex::<5>::from_notation("c3 d2");
+        assert_eq!(Hex::<5>::from_notation(&pos.notation()).hash(), pos.hash());
+    }
+
+    #[test]
+    fn assert_no_draws() {
+        for _ in 0..20 {
+            let mut pos = Hex::<7>::default();
+            while pos.game_state() == GameState::Ongoing {
+                let moves = pos.get_legal_moves();
+                pos.make_move(moves[random_index(moves.len())]);
+            }
+
+            assert_eq!(pos.game_state(), GameState::Loss);
+        }
+    }
+}
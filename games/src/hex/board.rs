@@ -0,0 +1,176 @@
+use super::moves::Move;
+use crate::bitboard::WideBitboard;
+
+/// Hex board of `N` by `N` cells, where black connects the top and bottom edge and white the
+/// left and right edge. Cells are stored row by row with a spare column at the end of every row,
+/// so shifts never connect one row with the next.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Board<const N: usize> {
+    side_to_move: bool,
+    stones: [WideBitboard; 2],
+}
+
+impl<const N: usize> Default for Board<N> {
+    fn default() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID;
+
+        Board {
+            side_to_move: false,
+            stones: [WideBitboard::ZERO; 2],
+        }
+    }
+}
+
+impl<const N: usize> Board<N> {
+    const STRIDE: usize = N + 1;
+
+    const VALID: () = assert!(
+        N >= 1 && N * (N + 1) <= WideBitboard::BITS && N <= 26,
+        "Board does not fit into a wide bitboard."
+    );
+
+    const fn index(col: usize, row: usize) -> usize {
+        row * Self::STRIDE + col
+    }
+
+    /// Parses rows from top to bottom separated by `/`, where `x` marks a black stone, `o` a
+    /// white stone and `.` an empty cell. Black moves first, so the side to move follows from
+    /// the number of stones.
+    pub fn from_layout(layout: &str) -> Self {
+        let mut board = Board::default();
+
+        for (row, cells) in layout.split('/').take(N).enumerate() {
+            for (col, cell) in cells.chars().take(N).enumerate() {
+                let bit = WideBitboard::bit(Self::index(col, row));
+
+                match cell {
+                    'x' => board.stones[0] = board.stones[0] | bit,
+                    'o' => board.stones[1] = board.stones[1] | bit,
+                    _ => {}
+                }
+            }
+        }
+
+        board.side_to_move = board.stones[0].count_ones() > board.stones[1].count_ones();
+        board
+    }
+
+    pub fn layout(&self) -> String {
+        let rows: Vec<String> = (0..N)
+            .map(|row| (0..N).map(|col| self.cell(col, row)).collect())
+            .collect();
+
+        rows.join("/")
+    }
+
+    fn cell(&self, col: usize, row: usize) -> char {
+        let index = Self::index(col, row);
+
+        if self.stones[0].is_set(index) {
+            'x'
+        } else if self.stones[1].is_set(index) {
+            'o'
+        } else {
+            '.'
+        }
+    }
+
+    pub fn side_to_move(&self) -> bool {
+        self.side_to_move
+    }
+
+    pub fn stones(&self, side: usize) -> WideBitboard {
+        self.stones[side]
+    }
+
+    pub fn make_move(&mut self, mov: Move) {
+        let side = usize::from(self.side_to_move);
+        let bit = WideBitboard::bit(Self::index(mov.col(), mov.row()));
+
+        self.stones[side] = self.stones[side] | bit;
+        self.side_to_move ^= true;
+    }
+
+    pub fn get_moves(&self) -> Vec<Move> {
+        let occupied = self.stones[0] | self.stones[1];
+
+        (0..N)
+            .flat_map(|row| (0..N).map(move |col| (col, row)))
+            .filter(|&(col, row)| !occupied.is_set(Self::index(col, row)))
+            .map(|(col, row)| Move::new(col, row))
+            .collect()
+    }
+
+    /// Whether the stones of `side` connect its two edges, found by flooding the stones reached
+    /// from the first edge until nothing changes.
+    pub fn connected(&self, side: usize) -> bool {
+        let stones = self.stones[side];
+        let (start, end) = Self::edges(side);
+
+        let mut reached = stones & start;
+        loop {
+            let next = reached | (Self::neighbours(reached) & stones);
+            if next == reached {
+                return !(reached & end).is_zero();
+            }
+
+            reached = next;
+        }
+    }
+
+    fn neighbours(cells: WideBitboard) -> WideBitboard {
+        let stride = Self::STRIDE;
+
+        cells << 1
+            | cells >> 1
+            | cells << stride
+            | cells >> stride
+            | cells << (stride - 1)
+            | cells >> (stride - 1)
+    }
+
+    /// Cells of the two edges a side has to connect.
+    fn edges(side: usize) -> (WideBitboard, WideBitboard) {
+        let cells = |f: &dyn Fn(usize) -> usize| {
+            (0..N).fold(WideBitboard::ZERO, |edge, i| edge | WideBitboard::bit(f(i)))
+        };
+
+        if side == 0 {
+            (
+                cells(&|col| Self::index(col, 0)),
+                cells(&|col| Self::index(col, N - 1)),
+            )
+        } else {
+            (
+                cells(&|row| Self::index(0, row)),
+                cells(&|row| Self::index(N - 1, row)),
+            )
+        }
+    }
+
+    /// Rows are indented to show the rhombus of the board.
+    pub fn display(&self) -> String {
+        let mut board = String::from("  ");
+
+        for col in 0..N {
+            board.push_str(&format!(" {}", char::from(b'a' + col as u8)));
+        }
+
+        for row in 0..N {
+            board.push_str(&format!("\n{:>2}{}", row + 1, " ".repeat(row)));
+
+            for col in 0..N {
+                let cell = match self.cell(col, row) {
+                    'x' => 'X',
+                    'o' => 'O',
+                    _ => '.',
+                };
+
+                board.push_str(&format!(" {}", cell));
+            }
+        }
+
+        board
+    }
+}
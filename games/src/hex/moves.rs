@@ -0,0 +1,40 @@
+/// Cell given by column and row, written like `c4` with `a1` in the top left corner.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Move(pub u16);
+
+impl Move {
+    pub fn new(col: usize, row: usize) -> Self {
+        Move((col as u16) << 8 | row as u16)
+    }
+
+    pub fn col(self) -> usize {
+        usize::from(self.0 >> 8)
+    }
+
+    pub fn row(self) -> usize {
+        usize::from(self.0 & 0xFF)
+    }
+}
+
+impl std::fmt::Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            char::from(b'a' + self.col() as u8),
+            self.row() + 1
+        )
+    }
+}
+
+impl From<u32> for Move {
+    fn from(mov: u32) -> Self {
+        Move(mov as u16)
+    }
+}
+
+impl From<Move> for u32 {
+    fn from(mov: Move) -> Self {
+        u32::from(mov.0)
+    }
+}
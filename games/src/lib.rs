@@ -1,30 +1,165 @@
+pub mod bitboard;
+#[cfg(feature = "checkers")]
+pub mod checkers;
+#[cfg(feature = "chess")]
 pub mod chess;
+#[cfg(feature = "connect4")]
 pub mod connect4;
+#[cfg(feature = "einstein")]
+pub mod einstein;
+#[cfg(feature = "go")]
+pub mod go;
+#[cfg(feature = "hex")]
+pub mod hex;
+#[cfg(feature = "mnk")]
+pub mod mnk;
+#[cfg(feature = "othello")]
+pub mod othello;
+pub mod registry;
+#[cfg(feature = "tron")]
+pub mod tron;
+pub mod tune;
+#[cfg(feature = "whist")]
+pub mod whist;
 
-use mentor::Game;
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError},
+        Mutex, OnceLock,
+    },
+    time::Duration,
+};
 
-pub fn handle_input<F: FnMut(&str, Vec<&str>)>(abort: &std::sync::atomic::AtomicBool, mut f: F) {
-    loop {
-        let mut input = String::new();
-        let bytes_read = std::io::stdin().read_line(&mut input).unwrap();
+use mentor::{
+    mcts::{params::SearchParameter, settings::SearchSettings, Search},
+    Game, GameState,
+};
+use rand::Rng;
 
-        if bytes_read == 0 {
-            break;
-        }
+/// Lines of standard input, read on a separate thread so that commands arriving while a search
+/// finishes are kept for the next caller instead of being lost.
+fn input() -> &'static Mutex<Receiver<String>> {
+    static INPUT: OnceLock<Mutex<Receiver<String>>> = OnceLock::new();
+
+    INPUT.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+
+        std::thread::spawn(move || loop {
+            let mut input = String::new();
+            let bytes_read = std::io::stdin().read_line(&mut input).unwrap();
+
+            if bytes_read == 0 || sender.send(input).is_err() {
+                break;
+            }
+        });
+
+        Mutex::new(receiver)
+    })
+}
+
+pub fn handle_input<F: FnMut(&str, Vec<&str>)>(abort: &AtomicBool, mut f: F) {
+    while !abort.load(Ordering::Relaxed) {
+        // The lock is released before `f` runs, which may read input itself during a search.
+        let input = input()
+            .lock()
+            .unwrap()
+            .recv_timeout(Duration::from_millis(10));
+        let input = match input {
+            Ok(input) => input,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
 
         let commands = input.split_whitespace().collect::<Vec<_>>();
         let command = *commands.first().unwrap_or(&"oops");
 
         f(command, commands);
+    }
+}
 
-        if abort.load(std::sync::atomic::Ordering::Relaxed) {
-            break;
-        }
+/// Finalizer of splitmix64, which mixes the state of a game into its hash.
+pub fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Uniformly random index into `len` elements.
+pub fn random_index(len: usize) -> usize {
+    rand::thread_rng().gen_range(0..len)
+}
+
+/// Plays random moves, and draws the outcomes of chance positions by their probabilities, until
+/// the game is over or `max_plies` are played. Returns the last position and the plies played.
+pub fn rollout<G: Game>(pos: &G, max_plies: usize) -> (G, usize) {
+    let mut pos = pos.clone();
+    let mut plies = 0;
+
+    while plies < max_plies && pos.game_state() == GameState::Ongoing {
+        let mov = if pos.is_chance() {
+            let outcomes = pos.get_outcomes();
+            let total: f32 = outcomes.iter().map(|(_, p)| p).sum();
+            let mut sample = rand::random::<f32>() * total;
+
+            outcomes
+                .iter()
+                .find(|(_, p)| {
+                    sample -= p;
+                    sample < 0.0
+                })
+                .unwrap_or(&outcomes[outcomes.len() - 1])
+                .0
+        } else {
+            let moves = pos.get_legal_moves();
+            moves[random_index(moves.len())]
+        };
+
+        pos.make_move(mov);
+        plies += 1;
+    }
+
+    (pos, plies)
+}
+
+static BATCH_SIZE: AtomicUsize = AtomicUsize::new(1);
+
+/// Leaves the search evaluates together, which is set by the protocol option `BatchSize`.
+pub fn batch_size() -> usize {
+    BATCH_SIZE.load(Ordering::Relaxed)
+}
+
+pub fn set_batch_size(batch_size: usize) {
+    BATCH_SIZE.store(batch_size.max(1), Ordering::Relaxed);
+}
+
+/// Limits of a `go` command with a fixed `movetime` or `nodes`, searching for `default_time`
+/// milliseconds without either and until `stop` with `infinite`.
+pub fn fixed_settings(commands: &[&str], default_time: u128) -> SearchSettings {
+    let value = |name: &str| {
+        commands
+            .iter()
+            .position(|&c| c == name)
+            .and_then(|i| commands.get(i + 1))
+            .and_then(|v| v.parse::<u128>().ok())
+    };
+
+    let max_time = match (value("movetime"), value("nodes")) {
+        _ if commands.contains(&"infinite") => None,
+        (Some(movetime), _) => Some(movetime),
+        (None, Some(_)) => None,
+        (None, None) => Some(default_time),
+    };
+
+    SearchSettings {
+        max_time,
+        max_nodes: value("nodes").map_or(usize::MAX, |nodes| nodes as usize),
+        batch_size: batch_size(),
     }
 }
 
 pub trait GameProtocol {
-    type Game: mentor::Game;
+    type Game: mentor::Game + Send + Sync;
 
     const NAME: &'static str;
     const NEW_GAME: &'static str;
@@ -33,74 +168,161 @@ pub trait GameProtocol {
     const DEFAULT_POS: String;
 
     fn run(&mut self) {
-        let mut pos = Self::Game::default();
+        let mut pos = self.start_pos();
         let params = mentor::mcts::params::SearchParameter::default();
-        let mut search = mentor::mcts::Search::new(pos, 50_000);
-
-        handle_input(
-            &std::sync::atomic::AtomicBool::new(false),
-            |command, commands| match command {
-                "quit" => std::process::exit(0),
-                "setoption" => todo!(),
-                "position" => self.position(&mut pos, commands),
-                "isready" => println!("readyok"),
-                "go" => {
-                    self.go(&mut pos, &mut search, &params, commands);
-                }
-                "d" => println!("{}", pos),
-                _ if command == Self::NAME => {
-                    println!("id name mentor");
-                    println!("id author Felix Jablinski");
-                    self.options();
-                }
-                _ if command == Self::NEW_GAME => pos = Self::Game::default(),
-                _ => {}
+        let mut search = Self::new_search(&pos);
+
+        handle_input(&AtomicBool::new(false), |command, commands| match command {
+            "quit" => std::process::exit(0),
+            "setoption" => self.setoption(commands),
+            "position" => match self.position(&mut pos, commands) {
+                Ok(history) => search.set_history(history),
+                Err(err) => println!("info string {}", err),
             },
-        )
+            "isready" => println!("readyok"),
+            "go" => {
+                self.go(&mut pos, &mut search, &params, commands);
+            }
+            "d" => println!("{}", pos),
+            _ if command == Self::NAME => {
+                println!("id name mentor");
+                println!("id author Felix Jablinski");
+                println!("option name BatchSize type spin default 1 min 1 max 256");
+                self.options();
+                println!("{}ok", Self::NAME);
+            }
+            _ if command == Self::NEW_GAME => {
+                self.new_game();
+                pos = self.start_pos();
+                search.set_history(Vec::new());
+            }
+            _ => {}
+        })
     }
 
-    fn search_input(&mut self, abort: &std::sync::atomic::AtomicBool) {
+    /// Position of `startpos`, which games dealt at random keep until the next new game.
+    fn start_pos(&self) -> Self::Game {
+        Self::Game::default()
+    }
+
+    /// Called on the new game command before the position is reset to `start_pos`.
+    fn new_game(&mut self) {}
+
+    /// Search kept between the moves of a game, which games implementing `UnmakeMove` create
+    /// with `Search::with_unmake`.
+    fn new_search(pos: &Self::Game) -> Search<Self::Game> {
+        Search::new(pos.clone(), 50_000)
+    }
+
+    /// Searches on a separate thread while `stop` and `isready` are still answered, and prints the
+    /// best move once the search is done.
+    fn search(
+        &mut self,
+        pos: &Self::Game,
+        search: &mut Search<Self::Game>,
+        params: &SearchParameter,
+        settings: &SearchSettings,
+    ) {
+        let abort = AtomicBool::new(false);
+
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                let mov = search.run(Some(pos.clone()), settings, params, &abort, false);
+                println!("bestmove {}", mov);
+
+                abort.store(true, Ordering::Relaxed);
+            });
+
+            self.search_input(&abort);
+        });
+    }
+
+    fn search_input(&mut self, abort: &AtomicBool) {
         handle_input(abort, |command, _| match command {
             "quit" => std::process::exit(0),
             "isready" => println!("readyok"),
-            "stop" => abort.store(true, std::sync::atomic::Ordering::Relaxed),
+            "stop" => abort.store(true, Ordering::Relaxed),
             _ if command == Self::NAME => {
                 println!("id name mentor");
                 println!("id author Felix Jablinski");
+                println!("option name BatchSize type spin default 1 min 1 max 256");
                 self.options();
+                println!("{}ok", Self::NAME);
             }
             _ => {}
         });
     }
 
-    fn position(&mut self, pos: &mut Self::Game, commands: Vec<&str>) {
-        let mut moves_start = false;
+    fn setoption(&mut self, commands: Vec<&str>) {
+        let name = commands.iter().position(|&c| c == "name");
+        let value = commands.iter().position(|&c| c == "value");
 
-        for command in commands {
-            if moves_start {
-                let mov = command.parse::<u16>().unwrap();
-                pos.make_move(mov.into());
+        let Some(name) = name else {
+            return;
+        };
 
-                continue;
-            }
+        let (name, value) = match value {
+            Some(value) if value > name => (
+                commands[name + 1..value].join(" "),
+                commands[value + 1..].join(" "),
+            ),
+            _ => (commands[name + 1..].join(" "), String::new()),
+        };
 
-            match command {
-                "position" => {}
-                "startpos" => {
-                    *pos = Self::Game::default();
-                    moves_start = true;
-                }
-                _ if command == Self::NOTATION => {}
-                _ => {
-                    *pos = Self::Game::from_notation(command);
-                    moves_start = true;
-                }
+        if name == "BatchSize" {
+            match value.parse() {
+                Ok(batch_size) => set_batch_size(batch_size),
+                Err(_) => println!("info string invalid batch size {}", value),
             }
+
+            return;
+        }
+
+        self.set_option(&name, &value);
+    }
+
+    /// Handles `position startpos|<notation> <position> [moves <move>...]`. A position spanning
+    /// several tokens, like a FEN, ends at `moves` or the end of the line. On an unparseable or
+    /// illegal move the position is left unchanged. Returns the hashes of the positions before it, by which the
+    /// search detects repetitions.
+    fn position(&mut self, pos: &mut Self::Game, commands: Vec<&str>) -> Result<Vec<u64>, String> {
+        let commands = &commands[1.min(commands.len())..];
+        let moves_start = commands.iter().position(|&c| c == "moves");
+
+        let (mut new_pos, moves) = match commands {
+            ["startpos", moves @ ..] => (self.start_pos(), moves),
+            [notation, rest @ ..] if *notation == Self::NOTATION => match moves_start {
+                Some(i) if i > 1 => (
+                    Self::Game::from_notation(&commands[1..i].join(" ")),
+                    &commands[i..],
+                ),
+                None if !rest.is_empty() => (Self::Game::from_notation(&rest.join(" ")), &[][..]),
+                _ => return Err(format!("missing {}", Self::NOTATION)),
+            },
+            [position, moves @ ..] => (Self::Game::from_notation(position), moves),
+            [] => return Err("missing position".to_owned()),
+        };
+
+        let moves = moves.strip_prefix(&["moves"]).unwrap_or(moves);
+        let mut history = Vec::with_capacity(moves.len());
+        for &notation in moves {
+            let mov = new_pos
+                .parse_move(notation)
+                .ok_or_else(|| format!("illegal move {}", notation))?;
+
+            history.push(new_pos.hash());
+            new_pos.make_move(mov);
         }
+
+        *pos = new_pos;
+
+        Ok(history)
     }
 
     fn options(&mut self);
 
+    fn set_option(&mut self, name: &str, value: &str);
+
     fn go(
         &mut self,
         pos: &mut Self::Game,
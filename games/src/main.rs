@@ -1,7 +1,26 @@
-#[allow(unused_imports)]
-use games::GameProtocol;
+use games::{
+    registry::{self, Registered, Visitor},
+    GameProtocol,
+};
+
+struct Protocol;
+
+impl Visitor for Protocol {
+    type Output = ();
+
+    fn visit<G: Registered>(self) {
+        G::Protocol::default().run();
+    }
+}
 
 fn main() {
-    #[cfg(feature = "connect4")]
-    games::connect4::Connect4Protocol {}.run();
+    let args: Vec<String> = std::env::args().collect();
+
+    let result = registry::game_arg(&args)
+        .and_then(|game| registry::visit(game, Protocol).map_err(|err| err.to_string()));
+
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
 }
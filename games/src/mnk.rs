@@ -0,0 +1,197 @@
+mod board;
+mod moves;
+
+use std::fmt;
+
+use board::Board;
+use mentor::{
+    mcts::{params::SearchParameter, Search},
+    Game, GameState,
+};
+use moves::Move;
+
+use crate::{fixed_settings, mix, registry::Registered, rollout, GameProtocol};
+
+/// Game of `K` in a row on a `W` by `H` board, optionally with gravity.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct Mnk<const W: usize, const H: usize, const K: usize, const GRAVITY: bool> {
+    board: Board<W, H, K, GRAVITY>,
+}
+
+pub type TicTacToe = Mnk<3, 3, 3, false>;
+pub type Connect5 = Mnk<9, 6, 5, true>;
+pub type Connect4Large = Mnk<8, 7, 4, true>;
+pub type Gomoku = Mnk<15, 15, 5, false>;
+
+impl<const W: usize, const H: usize, const K: usize, const GRAVITY: bool> fmt::Display
+    for Mnk<W, H, K, GRAVITY>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.board.display())
+    }
+}
+
+impl<const W: usize, const H: usize, const K: usize, const GRAVITY: bool> Game
+    for Mnk<W, H, K, GRAVITY>
+{
+    type Move = Move;
+
+    /// Accepts a layout as written by `notation`, or moves separated by whitespace, e.g.
+    /// `h8 i9 h9` or `d d e` with gravity. Moves after an illegal one are ignored.
+    fn from_notation(notation: &str) -> Self {
+        if notation.contains('/') {
+            return Mnk {
+                board: Board::from_layout(notation),
+            };
+        }
+
+        let mut pos = Self::default();
+
+        for notation in notation.split_whitespace() {
+            let Some(mov) = pos.parse_move(notation) else {
+                break;
+            };
+
+            pos.make_move(mov);
+        }
+
+        pos
+    }
+
+    fn notation(&self) -> String {
+        self.board.layout()
+    }
+
+    fn side_to_move(&self) -> usize {
+        usize::from(self.board.side_to_move())
+    }
+
+    fn game_state(&self) -> GameState {
+        if Board::<W, H, K, GRAVITY>::alignment(self.board.current() ^ self.board.mask()) {
+            return GameState::Loss;
+        }
+
+        if self.board.is_full() {
+            return GameState::Draw;
+        }
+
+        GameState::Ongoing
+    }
+
+    fn hash(&self) -> u64 {
+        self.board
+            .current()
+            .0
+            .iter()
+            .chain(self.board.mask().0.iter())
+            .fold(0, |hash, &word| mix(hash ^ word))
+    }
+
+    fn get_value(&mut self) -> f32 {
+        let side_to_move = self.side_to_move();
+        let (pos, iterations) = rollout(self, usize::MAX);
+
+        let reward = 1.0 - (iterations as f32 / (W * H) as f32).min(1.0) * 0.5;
+        match pos.game_state() {
+            GameState::Draw => 0.0,
+            _ if side_to_move == pos.side_to_move() => -reward,
+            _ => reward,
+        }
+    }
+
+    fn get_policy(&mut self, moves: &[Self::Move]) -> Vec<f32> {
+        vec![1.0 / moves.len() as f32; moves.len()]
+    }
+
+    fn make_move(&mut self, mov: Self::Move) {
+        self.board.make_move(mov);
+    }
+
+    fn get_legal_moves(&self) -> Vec<Self::Move> {
+        if self.game_state() != GameState::Ongoing {
+            return Vec::new();
+        }
+
+        self.board.get_moves()
+    }
+}
+
+impl Registered for TicTacToe {
+    type Protocol = MnkProtocol<3, 3, 3, false>;
+
+    const NAME: &'static str = "tictactoe";
+}
+
+impl Registered for Connect5 {
+    type Protocol = MnkProtocol<9, 6, 5, true>;
+
+    const NAME: &'static str = "connect5";
+}
+
+impl Registered for Connect4Large {
+    type Protocol = MnkProtocol<8, 7, 4, true>;
+
+    const NAME: &'static str = "connect4-8x7";
+}
+
+impl Registered for Gomoku {
+    type Protocol = MnkProtocol<15, 15, 5, false>;
+
+    const NAME: &'static str = "gomoku";
+}
+
+#[derive(Default)]
+pub struct MnkProtocol<const W: usize, const H: usize, const K: usize, const GRAVITY: bool>;
+
+impl<const W: usize, const H: usize, const K: usize, const GRAVITY: bool> GameProtocol
+    for MnkProtocol<W, H, K, GRAVITY>
+{
+    type Game = Mnk<W, H, K, GRAVITY>;
+
+    const NAME: &'static str = "uci";
+    const NEW_GAME: &'static str = "newgame";
+    const NOTATION: &'static str = "layout";
+
+    const DEFAULT_POS: String = String::new();
+
+    fn options(&mut self) {}
+
+    fn set_option(&mut self, _: &str, _: &str) {}
+
+    fn go(
+        &mut self,
+        pos: &mut Self::Game,
+        search: &mut Search<Self::Game>,
+        params: &SearchParameter,
+        commands: Vec<&str>,
+    ) {
+        self.search(pos, search, params, &fixed_settings(&commands, 5_000));
+    }
+}
+
+#[cfg(test)]
+mod mnk_test {
+    use super::{Connect5, Gomoku, TicTacToe};
+    use mentor::{Game, GameState};
+
+    #[test]
+    fn assert_game_state() {
+        let pos = TicTacToe::from_notation("a1 a2 b1 b2 c1");
+        assert_eq!(pos.game_state(), GameState::Loss);
+        assert!(pos.get_legal_moves().is_empty());
+
+        let pos = TicTacToe::from_notation("a1 b2 a2 a3 c1 b1 b3 c2 c3");
+        assert_eq!(pos.game_state(), GameState::Draw);
+
+        let pos = Connect5::from_notation("a b a b a b a b");
+        assert_eq!(pos.game_state(), GameState::Ongoing);
+        assert_eq!(pos.get_legal_moves().len(), 9);
+        assert_eq!(
+            Connect5::from_notation(&pos.notation()).notation(),
+            pos.notation()
+        );
+
+        let pos = Gomoku::from_notation("h8 a1 i9 a2 j10 a3 k11 a4 l12");
+        assert_eq!(pos.game_state(), GameState::Loss);
+    }
+}
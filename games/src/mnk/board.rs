@@ -0,0 +1,216 @@
+use super::moves::Move;
+use crate::bitboard::WideBitboard;
+
+/// Board of `W` columns and `H` rows on which `K` stones in a row win. With `GRAVITY` stones drop
+/// to the lowest empty cell of a column like in Connect 4.
+///
+/// Cells are stored column by column with a spare row on top of every column, so the shifts of
+/// `alignment` never wrap from one column into the next.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Board<const W: usize, const H: usize, const K: usize, const GRAVITY: bool> {
+    side_to_move: bool,
+    current: WideBitboard,
+    mask: WideBitboard,
+}
+
+impl<const W: usize, const H: usize, const K: usize, const GRAVITY: bool> Default
+    for Board<W, H, K, GRAVITY>
+{
+    fn default() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID;
+
+        Board {
+            side_to_move: false,
+            current: WideBitboard::ZERO,
+            mask: WideBitboard::ZERO,
+        }
+    }
+}
+
+impl<const W: usize, const H: usize, const K: usize, const GRAVITY: bool> Board<W, H, K, GRAVITY> {
+    const VALID: () = assert!(
+        W * (H + 1) <= WideBitboard::BITS && W <= 26 && K >= 1 && K <= if W > H { W } else { H },
+        "Board does not fit into a wide bitboard."
+    );
+
+    const fn index(col: usize, row: usize) -> usize {
+        col * (H + 1) + row
+    }
+
+    /// Parses rows from top to bottom separated by `/`, where `o` marks a stone of the first
+    /// player, `x` one of the second player and `.` an empty cell.
+    pub fn from_layout(layout: &str) -> Self {
+        let mut first = WideBitboard::ZERO;
+        let mut second = WideBitboard::ZERO;
+
+        for (i, row) in layout.split('/').take(H).enumerate() {
+            for (col, cell) in row.chars().take(W).enumerate() {
+                let bit = WideBitboard::bit(Self::index(col, H - 1 - i));
+
+                match cell {
+                    'o' => first = first | bit,
+                    'x' => second = second | bit,
+                    _ => {}
+                }
+            }
+        }
+
+        let side_to_move = first.count_ones() > second.count_ones();
+
+        Board {
+            side_to_move,
+            current: if side_to_move { second } else { first },
+            mask: first | second,
+        }
+    }
+
+    pub fn layout(&self) -> String {
+        let rows: Vec<String> = (0..H)
+            .rev()
+            .map(|row| (0..W).map(|col| self.cell(col, row)).collect())
+            .collect();
+
+        rows.join("/")
+    }
+
+    /// Layout character of a cell.
+    fn cell(&self, col: usize, row: usize) -> char {
+        let index = Self::index(col, row);
+
+        if !self.mask.is_set(index) {
+            '.'
+        } else if self.current.is_set(index) != self.side_to_move {
+            'o'
+        } else {
+            'x'
+        }
+    }
+
+    pub fn make_move(&mut self, mov: Move) {
+        let row = mov.row().unwrap_or_else(|| self.height(mov.col()));
+
+        self.current = self.current ^ self.mask;
+        self.mask = self.mask | WideBitboard::bit(Self::index(mov.col(), row));
+        self.side_to_move ^= true;
+    }
+
+    pub fn get_moves(&self) -> Vec<Move> {
+        if GRAVITY {
+            return (0..W)
+                .filter(|&col| self.height(col) < H)
+                .map(Move::column)
+                .collect();
+        }
+
+        (0..W)
+            .flat_map(|col| (0..H).map(move |row| (col, row)))
+            .filter(|&(col, row)| !self.mask.is_set(Self::index(col, row)))
+            .map(|(col, row)| Move::cell(col, row))
+            .collect()
+    }
+
+    /// Number of stones in a column.
+    fn height(&self, col: usize) -> usize {
+        (0..H)
+            .find(|&row| !self.mask.is_set(Self::index(col, row)))
+            .unwrap_or(H)
+    }
+
+    pub fn side_to_move(&self) -> bool {
+        self.side_to_move
+    }
+
+    pub fn current(&self) -> WideBitboard {
+        self.current
+    }
+
+    pub fn mask(&self) -> WideBitboard {
+        self.mask
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.mask.count_ones() as usize == W * H
+    }
+
+    pub fn display(&self) -> String {
+        let separator = format!("+{}\n", "---+".repeat(W));
+        let mut board = String::new();
+
+        for row in (0..H).rev() {
+            board.push_str(&separator);
+
+            for col in 0..W {
+                let cell = match self.cell(col, row) {
+                    'o' => 'O',
+                    'x' => 'X',
+                    _ => ' ',
+                };
+
+                board.push_str(&format!("| {} ", cell));
+            }
+
+            board.push_str(&format!("| {}\n", row + 1));
+        }
+
+        board.push_str(&separator);
+        for col in 0..W {
+            board.push_str(&format!("  {} ", char::from(b'a' + col as u8)));
+        }
+
+        board
+    }
+
+    /// Whether `pos` has `K` stones in a row along a column, row or diagonal. Runs are doubled
+    /// in length with every step, so a line of `K` needs about `log2(K)` shifts per direction.
+    pub fn alignment(pos: WideBitboard) -> bool {
+        [1, H + 1, H, H + 2].into_iter().any(|shift| {
+            let mut run = pos;
+            let mut length = 1;
+
+            while length < K && !run.is_zero() {
+                let step = length.min(K - length);
+                run = run & (run >> (step * shift));
+                length += step;
+            }
+
+            !run.is_zero()
+        })
+    }
+}
+
+#[cfg(test)]
+mod board_test {
+    use super::Board;
+    use crate::mnk::moves::Move;
+
+    #[test]
+    fn assert_alignment() {
+        let board = Board::<15, 15, 5, false>::from_layout(
+            "...............\
+            /...............\
+            /....o..........\
+            /.....o.........\
+            /......o........\
+            /.......o.......\
+            /........o......",
+        );
+        assert!(Board::<15, 15, 5, false>::alignment(board.mask()));
+
+        let board = Board::<15, 15, 5, false>::from_layout(
+            "/////////////\
+            /oooo.oooo......",
+        );
+        assert!(!Board::<15, 15, 5, false>::alignment(board.mask()));
+
+        let mut board = Board::<3, 3, 3, false>::default();
+        for mov in [(0, 2), (1, 1), (2, 0)] {
+            board.make_move(Move::cell(mov.0, mov.1));
+            board.make_move(Move::cell(mov.0, (mov.1 + 1) % 3));
+        }
+        assert!(Board::<3, 3, 3, false>::alignment(board.current()));
+        assert!(!Board::<3, 3, 3, false>::alignment(
+            board.current() ^ board.mask()
+        ));
+    }
+}
@@ -0,0 +1,48 @@
+/// Cell given by column and row, or only by column in games with gravity.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Move(pub u16);
+
+impl Move {
+    const COLUMN_ONLY: u16 = 0xFF;
+
+    pub fn cell(col: usize, row: usize) -> Self {
+        Move((col as u16) << 8 | row as u16)
+    }
+
+    pub fn column(col: usize) -> Self {
+        Move((col as u16) << 8 | Self::COLUMN_ONLY)
+    }
+
+    pub fn col(self) -> usize {
+        usize::from(self.0 >> 8)
+    }
+
+    /// Row of the cell, which is `None` for moves that drop into a column.
+    pub fn row(self) -> Option<usize> {
+        let row = self.0 & 0xFF;
+        (row != Self::COLUMN_ONLY).then_some(usize::from(row))
+    }
+}
+
+impl std::fmt::Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let col = char::from(b'a' + self.col() as u8);
+
+        match self.row() {
+            Some(row) => write!(f, "{}{}", col, row + 1),
+            None => write!(f, "{}", col),
+        }
+    }
+}
+
+impl From<u32> for Move {
+    fn from(mov: u32) -> Self {
+        Move(mov as u16)
+    }
+}
+
+impl From<Move> for u32 {
+    fn from(mov: Move) -> Self {
+        u32::from(mov.0)
+    }
+}
@@ -0,0 +1,182 @@
+mod board;
+mod moves;
+
+use std::fmt;
+
+use board::Board;
+use mentor::{
+    mcts::{params::SearchParameter, Search},
+    Game, GameState,
+};
+use moves::Move;
+
+use crate::{fixed_settings, mix, registry::Registered, rollout, GameProtocol};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct Othello {
+    board: Board,
+}
+
+impl fmt::Display for Othello {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.board.display())
+    }
+}
+
+impl Game for Othello {
+    type Move = Move;
+
+    /// Accepts a layout as written by `notation`, or moves separated by whitespace or written
+    /// without separators, e.g. `f5d6c3`. Moves after an illegal one are ignored.
+    fn from_notation(notation: &str) -> Self {
+        if notation.contains('/') {
+            return Othello {
+                board: Board::from_layout(notation),
+            };
+        }
+
+        let mut pos = Self::default();
+        let moves = notation.split_whitespace().flat_map(|token| {
+            let chars: Vec<char> = token.chars().collect();
+
+            if token == "pass" || chars.len() <= 2 {
+                vec![token.to_owned()]
+            } else {
+                chars
+                    .chunks(2)
+                    .map(|chunk| chunk.iter().collect())
+                    .collect()
+            }
+        });
+
+        for notation in moves {
+            let Some(mov) = pos.parse_move(&notation) else {
+                break;
+            };
+
+            pos.make_move(mov);
+        }
+
+        pos
+    }
+
+    fn notation(&self) -> String {
+        self.board.layout()
+    }
+
+    fn side_to_move(&self) -> usize {
+        usize::from(self.board.side_to_move())
+    }
+
+    fn game_state(&self) -> GameState {
+        if !self.board.is_over() {
+            return GameState::Ongoing;
+        }
+
+        let own = self.board.own().count_ones();
+        let opponent = self.board.opponent().count_ones();
+
+        match own.cmp(&opponent) {
+            std::cmp::Ordering::Greater => GameState::Win,
+            std::cmp::Ordering::Less => GameState::Loss,
+            std::cmp::Ordering::Equal => GameState::Draw,
+        }
+    }
+
+    fn hash(&self) -> u64 {
+        let (black, white) = self.board.discs();
+        let side = u64::from(self.board.side_to_move());
+
+        mix(black ^ mix(white ^ mix(side)))
+    }
+
+    fn get_value(&mut self) -> f32 {
+        let side_to_move = self.side_to_move();
+        let (pos, _) = rollout(self, usize::MAX);
+
+        match pos.game_state() {
+            GameState::Draw => 0.0,
+            GameState::Win if side_to_move == pos.side_to_move() => 1.0,
+            GameState::Loss if side_to_move != pos.side_to_move() => 1.0,
+            _ => -1.0,
+        }
+    }
+
+    fn get_policy(&mut self, moves: &[Self::Move]) -> Vec<f32> {
+        vec![1.0 / moves.len() as f32; moves.len()]
+    }
+
+    fn make_move(&mut self, mov: Self::Move) {
+        self.board.make_move(mov);
+    }
+
+    fn get_legal_moves(&self) -> Vec<Self::Move> {
+        if self.game_state() != GameState::Ongoing {
+            return Vec::new();
+        }
+
+        self.board.get_moves()
+    }
+}
+
+impl Registered for Othello {
+    type Protocol = OthelloProtocol;
+
+    const NAME: &'static str = "othello";
+}
+
+#[derive(Default)]
+pub struct OthelloProtocol;
+
+impl GameProtocol for OthelloProtocol {
+    type Game = Othello;
+
+    const NAME: &'static str = "uci";
+    const NEW_GAME: &'static str = "newgame";
+    const NOTATION: &'static str = "layout";
+
+    const DEFAULT_POS: String = String::new();
+
+    fn options(&mut self) {}
+
+    fn set_option(&mut self, _: &str, _: &str) {}
+
+    fn go(
+        &mut self,
+        pos: &mut Self::Game,
+        search: &mut Search<Self::Game>,
+        params: &SearchParameter,
+        commands: Vec<&str>,
+    ) {
+        self.search(pos, search, params, &fixed_settings(&commands, 5_000));
+    }
+}
+
+#[cfg(test)]
+mod othello_test {
+    use super::Othello;
+    use mentor::{Game, GameState};
+
+    #[test]
+    fn assert_pass_and_game_over() {
+        // White has no move, so black keeps moving after a pass.
+        let pos = Othello::from_notation(
+            "xxxxxxxx/xxxxxxxx/xxxxxxxx/xxxxxxxx/xxxxxxxx/xxxxxxxx/xxxxxxx./xxxxxxo. o",
+        );
+        let moves = pos.get_legal_moves();
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].to_string(), "pass");
+
+        let mut pos = pos;
+        pos.make_move(moves[0]);
+        assert_eq!(pos.get_legal_moves()[0].to_string(), "h8");
+
+        pos.make_move(pos.parse_move("h8").unwrap());
+        assert_eq!(pos.game_state(), GameState::Loss);
+        assert_eq!(pos.notation().split('/').next_back(), Some("xxxxxxxx o"));
+
+        let pos = Othello::from_notation("f5d6c3d3c4");
+        assert_eq!(pos.side_to_move(), 1);
+        assert_eq!(Othello::from_notation(&pos.notation()).hash(), pos.hash());
+    }
+}
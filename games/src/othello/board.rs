@@ -0,0 +1,254 @@
+use super::moves::Move;
+
+const NOT_A_FILE: u64 = 0xfefe_fefe_fefe_fefe;
+const NOT_H_FILE: u64 = 0x7f7f_7f7f_7f7f_7f7f;
+
+/// Shifts of the eight directions with the mask that removes bits wrapped around the board.
+const DIRECTIONS: [(i32, u64); 8] = [
+    (1, NOT_A_FILE),
+    (-1, NOT_H_FILE),
+    (8, u64::MAX),
+    (-8, u64::MAX),
+    (9, NOT_A_FILE),
+    (-9, NOT_H_FILE),
+    (7, NOT_H_FILE),
+    (-7, NOT_A_FILE),
+];
+
+#[inline]
+fn shift(bitboard: u64, (shift, mask): (i32, u64)) -> u64 {
+    if shift > 0 {
+        (bitboard << shift) & mask
+    } else {
+        (bitboard >> -shift) & mask
+    }
+}
+
+/// Discs of the side to move and of its opponent, with bit `row * 8 + col` and `a1` in the top
+/// left corner.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Board {
+    side_to_move: bool,
+    own: u64,
+    opponent: u64,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board {
+            side_to_move: false,
+            own: 1 << 28 | 1 << 35,
+            opponent: 1 << 27 | 1 << 36,
+        }
+    }
+}
+
+impl Board {
+    /// Parses rows from top to bottom separated by `/`, where `x` marks a black disc, `o` a
+    /// white disc and `.` an empty square, followed by the side to move.
+    pub fn from_layout(layout: &str) -> Board {
+        let mut parts = layout.split_whitespace();
+        let mut discs = [0u64; 2];
+
+        for (row, cells) in parts
+            .next()
+            .unwrap_or_default()
+            .split('/')
+            .take(8)
+            .enumerate()
+        {
+            for (col, cell) in cells.chars().take(8).enumerate() {
+                match cell {
+                    'x' => discs[0] |= 1 << (row * 8 + col),
+                    'o' => discs[1] |= 1 << (row * 8 + col),
+                    _ => {}
+                }
+            }
+        }
+
+        let side_to_move = parts.next() == Some("o");
+        let side = usize::from(side_to_move);
+
+        Board {
+            side_to_move,
+            own: discs[side],
+            opponent: discs[side ^ 1],
+        }
+    }
+
+    pub fn layout(&self) -> String {
+        let rows: Vec<String> = (0..8)
+            .map(|row| (0..8).map(|col| self.cell(row * 8 + col)).collect())
+            .collect();
+
+        format!(
+            "{} {}",
+            rows.join("/"),
+            if self.side_to_move { 'o' } else { 'x' }
+        )
+    }
+
+    fn cell(&self, square: usize) -> char {
+        let (black, white) = self.discs();
+
+        if black >> square & 1 != 0 {
+            'x'
+        } else if white >> square & 1 != 0 {
+            'o'
+        } else {
+            '.'
+        }
+    }
+
+    /// Discs of black and white.
+    pub fn discs(&self) -> (u64, u64) {
+        if self.side_to_move {
+            (self.opponent, self.own)
+        } else {
+            (self.own, self.opponent)
+        }
+    }
+
+    pub fn side_to_move(&self) -> bool {
+        self.side_to_move
+    }
+
+    pub fn own(&self) -> u64 {
+        self.own
+    }
+
+    pub fn opponent(&self) -> u64 {
+        self.opponent
+    }
+
+    /// Squares where `own` flanks at least one line of `opponent` discs.
+    pub fn moves(own: u64, opponent: u64) -> u64 {
+        let empty = !(own | opponent);
+        let mut moves = 0;
+
+        for direction in DIRECTIONS {
+            let mut line = shift(own, direction) & opponent;
+            for _ in 0..5 {
+                line |= shift(line, direction) & opponent;
+            }
+
+            moves |= shift(line, direction) & empty;
+        }
+
+        moves
+    }
+
+    fn flips(&self, square: u64) -> u64 {
+        let mut flips = 0;
+
+        for direction in DIRECTIONS {
+            let mut line = 0;
+            let mut next = shift(square, direction);
+
+            while next & self.opponent != 0 {
+                line |= next;
+                next = shift(next, direction);
+            }
+
+            if next & self.own != 0 {
+                flips |= line;
+            }
+        }
+
+        flips
+    }
+
+    pub fn make_move(&mut self, mov: Move) {
+        if mov != Move::PASS {
+            let square = 1 << mov.0;
+            let flips = self.flips(square);
+
+            self.own |= square | flips;
+            self.opponent &= !flips;
+        }
+
+        std::mem::swap(&mut self.own, &mut self.opponent);
+        self.side_to_move ^= true;
+    }
+
+    pub fn get_moves(&self) -> Vec<Move> {
+        let mut moves = Board::moves(self.own, self.opponent);
+        if moves == 0 {
+            return vec![Move::PASS];
+        }
+
+        let mut list = Vec::with_capacity(moves.count_ones() as usize);
+        while moves != 0 {
+            list.push(Move(moves.trailing_zeros() as u16));
+            moves &= moves - 1;
+        }
+
+        list
+    }
+
+    /// Neither side can move anymore.
+    pub fn is_over(&self) -> bool {
+        Board::moves(self.own, self.opponent) == 0 && Board::moves(self.opponent, self.own) == 0
+    }
+
+    pub fn display(&self) -> String {
+        let mut board = String::new();
+
+        for row in 0..8 {
+            board.push_str("+---+---+---+---+---+---+---+---+\n");
+
+            for col in 0..8 {
+                let cell = match self.cell(row * 8 + col) {
+                    'x' => 'X',
+                    'o' => 'O',
+                    _ => ' ',
+                };
+
+                board.push_str(&format!("| {} ", cell));
+            }
+
+            board.push_str(&format!("| {}\n", row + 1));
+        }
+
+        board.push_str("+---+---+---+---+---+---+---+---+\n");
+        board.push_str("  a   b   c   d   e   f   g   h");
+        board
+    }
+}
+
+#[cfg(test)]
+mod board_test {
+    use super::Board;
+
+    fn perft(board: &Board, depth: usize) -> u64 {
+        if board.is_over() {
+            return 1;
+        }
+
+        if depth == 0 {
+            return 1;
+        }
+
+        board
+            .get_moves()
+            .into_iter()
+            .map(|mov| {
+                let mut board = *board;
+                board.make_move(mov);
+
+                perft(&board, depth - 1)
+            })
+            .sum()
+    }
+
+    #[test]
+    fn assert_perft() {
+        let board = Board::default();
+
+        for (depth, expected) in [4, 12, 56, 244, 1396, 8200, 55092].into_iter().enumerate() {
+            assert_eq!(perft(&board, depth + 1), expected, "Perft {}", depth + 1);
+        }
+
+        assert_eq!(Board::from_layout(&board.layout()).layout(), board.layout());
+    }
+}
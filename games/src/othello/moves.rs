@@ -0,0 +1,30 @@
+/// Square index from `a1` in the top left corner to `h8`, or a pass.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Move(pub u16);
+
+impl Move {
+    pub const PASS: Move = Move(64);
+}
+
+impl std::fmt::Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if *self == Move::PASS {
+            return write!(f, "pass");
+        }
+
+        let col = char::from(b'a' + (self.0 % 8) as u8);
+        write!(f, "{}{}", col, self.0 / 8 + 1)
+    }
+}
+
+impl From<u32> for Move {
+    fn from(mov: u32) -> Self {
+        Move(mov as u16)
+    }
+}
+
+impl From<Move> for u32 {
+    fn from(mov: Move) -> Self {
+        u32::from(mov.0)
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+
+use mentor::Game;
+
+use crate::GameProtocol;
+
+/// Game that can be selected by name at runtime.
+pub trait Registered: Game {
+    type Protocol: GameProtocol<Game = Self> + Default;
+
+    const NAME: &'static str;
+
+    /// Start position of a new game drawn from `seed`, which only games with a random setup like
+    /// a deal of cards make use of.
+    fn start(_seed: u64) -> Self {
+        Self::default()
+    }
+}
+
+/// Generic code to run for the selected game, e.g. its protocol or the data generator.
+pub trait Visitor {
+    type Output;
+
+    fn visit<G: Registered>(self) -> Self::Output;
+}
+
+#[derive(Debug)]
+pub enum RegistryError {
+    Unknown(String),
+    NotCompiled {
+        game: &'static str,
+        feature: &'static str,
+    },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::Unknown(name) => write!(
+                f,
+                "Unknown game {:?}, expected one of: {}.",
+                name,
+                GAMES.join(", ")
+            ),
+            RegistryError::NotCompiled { game, feature } => write!(
+                f,
+                "Game {:?} is not compiled in, rebuild with `--features {}`.",
+                game, feature
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+macro_rules! registry {
+    ($($name:literal => $feature:literal, $game:ty;)+) => {
+        /// Every game known to the registry, whether or not its feature is enabled.
+        pub const GAMES: &[&str] = &[$($name),+];
+
+        /// Calls the visitor with the game registered under `name`.
+        #[allow(unused_variables)]
+        pub fn visit<V: Visitor>(name: &str, visitor: V) -> Result<V::Output, RegistryError> {
+            match name {
+                $(
+                    $name => {
+                        #[cfg(feature = $feature)]
+                        return Ok(visitor.visit::<$game>());
+
+                        #[cfg(not(feature = $feature))]
+                        return Err(RegistryError::NotCompiled {
+                            game: $name,
+                            feature: $feature,
+                        });
+                    }
+                )+
+                _ => Err(RegistryError::Unknown(name.to_owned())),
+            }
+        }
+    };
+}
+
+registry! {
+    "chess" => "chess", crate::chess::Chess;
+    "connect4" => "connect4", crate::connect4::Connect4;
+    "tictactoe" => "mnk", crate::mnk::TicTacToe;
+    "connect5" => "mnk", crate::mnk::Connect5;
+    "connect4-8x7" => "mnk", crate::mnk::Connect4Large;
+    "gomoku" => "mnk", crate::mnk::Gomoku;
+    "othello" => "othello", crate::othello::Othello;
+    "hex" => "hex", crate::hex::Hex11;
+    "go" => "go", crate::go::Go;
+    "checkers" => "checkers", crate::checkers::Checkers;
+    "einstein" => "einstein", crate::einstein::Einstein;
+    "tron" => "tron", crate::tron::Tron;
+    "whist" => "whist", crate::whist::Whist;
+}
+
+/// Value of the `--game` argument.
+pub fn game_arg(args: &[String]) -> Result<&str, String> {
+    args.iter()
+        .position(|arg| arg == "--game")
+        .map(|i| {
+            args.get(i + 1)
+                .map(String::as_str)
+                .ok_or_else(|| "Argument for game.".to_owned())
+        })
+        .unwrap_or_else(|| {
+            Err(format!(
+                "Missing --game, expected one of: {}.",
+                GAMES.join(", ")
+            ))
+        })
+}
+
+#[cfg(test)]
+mod registry_test {
+    use super::{visit, Registered, RegistryError, Visitor};
+
+    struct Name;
+
+    impl Visitor for Name {
+        type Output = &'static str;
+
+        fn visit<G: Registered>(self) -> Self::Output {
+            G::NAME
+        }
+    }
+
+    #[test]
+    fn assert_visit() {
+        #[cfg(feature = "chess")]
+        assert_eq!(visit("chess", Name).unwrap(), "chess");
+        #[cfg(feature = "connect4")]
+        assert_eq!(visit("connect4", Name).unwrap(), "connect4");
+        #[cfg(feature = "mnk")]
+        assert_eq!(visit("gomoku", Name).unwrap(), "gomoku");
+
+        assert!(matches!(
+            visit("checkers!", Name),
+            Err(RegistryError::Unknown(_))
+        ));
+    }
+}
@@ -0,0 +1,253 @@
+mod board;
+mod moves;
+
+use std::fmt;
+
+use board::{Board, PLAYERS};
+use mentor::{
+    mcts::{
+        params::{SearchParameter, Strategy},
+        Search,
+    },
+    Game, GameState, Rewards, MAX_PLAYERS,
+};
+use moves::Move;
+
+use crate::{fixed_settings, mix, registry::Registered, rollout, GameProtocol};
+
+/// Light cycles for four players taking turns. Every player moves its head to an empty
+/// neighbouring square and leaves a wall behind. A player who can't move anymore is out, and the
+/// last one left wins.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tron {
+    board: Board,
+}
+
+impl Tron {
+    /// Plays random moves until a single player is left.
+    fn rollout(&self) -> Rewards {
+        let (pos, _) = rollout(self, usize::MAX);
+
+        pos.game_state().rewards(pos.side_to_move())
+    }
+}
+
+impl fmt::Display for Tron {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.board.display())
+    }
+}
+
+impl Game for Tron {
+    type Move = Move;
+
+    const PLAYERS: usize = PLAYERS;
+
+    /// Accepts a layout followed by the player to move, e.g. `A......B/.../D......C A`, or
+    /// moves from the start position separated by whitespace. Moves after an illegal one are
+    /// ignored.
+    fn from_notation(notation: &str) -> Self {
+        let mut parts = notation.split_whitespace();
+
+        if notation.contains('/') {
+            let layout = parts.next().unwrap_or_default();
+            let side_to_move = match parts.next() {
+                Some(player @ ("A" | "B" | "C" | "D")) => usize::from(player.as_bytes()[0] - b'A'),
+                _ => 0,
+            };
+
+            return Tron {
+                board: Board::from_layout(layout, side_to_move),
+            };
+        }
+
+        let mut pos = Self::default();
+
+        for notation in parts {
+            let Some(mov) = pos.parse_move(notation) else {
+                break;
+            };
+
+            pos.make_move(mov);
+        }
+
+        pos
+    }
+
+    fn notation(&self) -> String {
+        let side = char::from(b'A' + self.side_to_move() as u8);
+
+        format!("{} {}", self.board.layout(), side)
+    }
+
+    fn side_to_move(&self) -> usize {
+        self.board.side_to_move()
+    }
+
+    fn game_state(&self) -> GameState {
+        if self.board.alive().count_ones() > 1 {
+            return GameState::Ongoing;
+        }
+
+        let mut results = [-1; MAX_PLAYERS];
+        for (player, result) in results.iter_mut().enumerate().take(PLAYERS) {
+            if self.board.is_alive(player) {
+                *result = 1;
+            }
+        }
+
+        GameState::Outcome(results)
+    }
+
+    fn hash(&self) -> u64 {
+        let heads = self
+            .board
+            .heads()
+            .iter()
+            .fold(0u64, |key, &head| key << 6 | u64::from(head));
+        let state = heads << 8 | u64::from(self.board.alive()) << 2;
+
+        mix(self.board.walls() ^ mix(state | self.side_to_move() as u64))
+    }
+
+    fn get_value(&mut self) -> f32 {
+        self.rollout()[self.side_to_move()]
+    }
+
+    fn get_rewards(positions: &mut [Self]) -> Vec<Rewards> {
+        positions.iter().map(Self::rollout).collect()
+    }
+
+    fn get_policy(&mut self, moves: &[Self::Move]) -> Vec<f32> {
+        vec![1.0 / moves.len() as f32; moves.len()]
+    }
+
+    fn make_move(&mut self, mov: Self::Move) {
+        self.board.make_move(mov);
+    }
+
+    fn get_legal_moves(&self) -> Vec<Self::Move> {
+        if self.game_state() != GameState::Ongoing {
+            return Vec::new();
+        }
+
+        self.board.get_moves()
+    }
+}
+
+impl Registered for Tron {
+    type Protocol = TronProtocol;
+
+    const NAME: &'static str = "tron";
+}
+
+#[derive(Default)]
+pub struct TronProtocol {
+    strategy: Strategy,
+}
+
+impl GameProtocol for TronProtocol {
+    type Game = Tron;
+
+    const NAME: &'static str = "uci";
+    const NEW_GAME: &'static str = "newgame";
+    const NOTATION: &'static str = "layout";
+
+    const DEFAULT_POS: String = String::new();
+
+    fn options(&mut self) {
+        println!("option name Strategy type combo default maxn var maxn var paranoid");
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) {
+        if name == "Strategy" {
+            self.strategy = match value {
+                "paranoid" => Strategy::Paranoid,
+                _ => Strategy::MaxN,
+            };
+        }
+    }
+
+    fn go(
+        &mut self,
+        pos: &mut Self::Game,
+        search: &mut Search<Self::Game>,
+        params: &SearchParameter,
+        commands: Vec<&str>,
+    ) {
+        let params = SearchParameter {
+            strategy: self.strategy,
+            ..*params
+        };
+
+        self.search(pos, search, &params, &fixed_settings(&commands, 5_000));
+    }
+}
+
+#[cfg(test)]
+mod tron_test {
+    use super::Tron;
+    use mentor::{
+        mcts::{
+            params::{SearchParameter, Strategy},
+            settings::SearchSettings,
+            Search,
+        },
+        Game, GameState,
+    };
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn assert_elimination() {
+        let mut pos = Tron::from_notation(
+            "A......B/......../......../......../......../......../.......#/D.....#C B",
+        );
+        assert_eq!(pos.get_legal_moves().len(), 2);
+
+        // C is out as it can't move anymore, so D is next after B.
+        pos.make_move(pos.parse_move("h7").unwrap());
+        assert_eq!(pos.side_to_move(), 3);
+        assert_eq!(pos.game_state(), GameState::Ongoing);
+        assert_eq!(
+            pos.notation(),
+            "A......#/.......B/......../......../......../......../.......#/D.....## D"
+        );
+
+        let mut pos = Tron::from_notation(
+            "B#....../.A....../......../......../......../......../......../........ A",
+        );
+        pos.make_move(pos.parse_move("a7").unwrap());
+        assert_eq!(pos.game_state(), GameState::Outcome([1, -1, -1, -1]));
+    }
+
+    #[test]
+    fn assert_trapping_move() {
+        // Moving to a7 leaves B without a move, which ends the game between the last two players.
+        let pos = Tron::from_notation(
+            "B#....../.A....../......../......../......../......../......../........ A",
+        );
+
+        for strategy in [Strategy::MaxN, Strategy::Paranoid] {
+            let params = SearchParameter {
+                strategy,
+                ..SearchParameter::default()
+            };
+            let settings = SearchSettings {
+                max_time: None,
+                max_nodes: 2_000,
+                batch_size: 1,
+            };
+
+            let mut search = Search::new(pos, 2_000);
+            let mov = search.run(
+                Some(pos),
+                &settings,
+                &params,
+                &AtomicBool::new(false),
+                false,
+            );
+
+            assert_eq!(mov.to_string(), "a7");
+        }
+    }
+}
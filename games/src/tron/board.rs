@@ -0,0 +1,190 @@
+use super::moves::Move;
+
+pub const PLAYERS: usize = 4;
+
+const SIZE: usize = 8;
+
+/// Corners the players start in, clockwise from `a1`.
+const START: [usize; PLAYERS] = [0, SIZE - 1, SIZE * SIZE - 1, SIZE * (SIZE - 1)];
+
+/// Board of 8x8 squares with `a1` in the bottom left corner. Every square a head has been on
+/// stays blocked for the rest of the game.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Board {
+    walls: u64,
+    heads: [u8; PLAYERS],
+    alive: u8,
+    side_to_move: usize,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board {
+            walls: START.iter().fold(0, |walls, &square| walls | 1 << square),
+            heads: START.map(|square| square as u8),
+            alive: (1 << PLAYERS) - 1,
+            side_to_move: 0,
+        }
+    }
+}
+
+impl Board {
+    /// Parses ranks from top to bottom separated by `/`, where `A` to `D` are the heads of the
+    /// players, `#` marks a blocked square and `.` an empty one. Players without a head are out.
+    pub fn from_layout(layout: &str, side_to_move: usize) -> Self {
+        let mut board = Board {
+            walls: 0,
+            heads: [0; PLAYERS],
+            alive: 0,
+            side_to_move,
+        };
+
+        for (i, rank) in layout.split('/').take(SIZE).enumerate() {
+            for (file, c) in rank.chars().take(SIZE).enumerate() {
+                let square = (SIZE - 1 - i) * SIZE + file;
+
+                match c {
+                    'A'..='D' => {
+                        let player = usize::from(c as u8 - b'A');
+                        board.heads[player] = square as u8;
+                        board.alive |= 1 << player;
+                    }
+                    '#' => {}
+                    _ => continue,
+                }
+
+                board.walls |= 1 << square;
+            }
+        }
+
+        if board.alive & 1 << side_to_move == 0 {
+            board.pass_turn();
+        }
+        board.eliminate_trapped();
+
+        board
+    }
+
+    pub fn layout(&self) -> String {
+        let ranks: Vec<String> = (0..SIZE)
+            .rev()
+            .map(|rank| {
+                (0..SIZE)
+                    .map(|file| self.cell(rank * SIZE + file))
+                    .collect()
+            })
+            .collect();
+
+        ranks.join("/")
+    }
+
+    fn cell(&self, square: usize) -> char {
+        if let Some(player) = (0..PLAYERS).find(|&p| self.is_alive(p) && self.head(p) == square) {
+            return char::from(b'A' + player as u8);
+        }
+
+        if self.walls & 1 << square != 0 {
+            '#'
+        } else {
+            '.'
+        }
+    }
+
+    pub fn side_to_move(&self) -> usize {
+        self.side_to_move
+    }
+
+    pub fn walls(&self) -> u64 {
+        self.walls
+    }
+
+    pub fn heads(&self) -> [u8; PLAYERS] {
+        self.heads
+    }
+
+    pub fn alive(&self) -> u8 {
+        self.alive
+    }
+
+    pub fn is_alive(&self, player: usize) -> bool {
+        self.alive & 1 << player != 0
+    }
+
+    fn head(&self, player: usize) -> usize {
+        usize::from(self.heads[player])
+    }
+
+    /// Empty squares next to the head of `player`.
+    fn free_neighbours(&self, player: usize) -> Vec<usize> {
+        let square = self.head(player);
+        let (file, rank) = (square % SIZE, square / SIZE);
+
+        let mut neighbours = Vec::with_capacity(4);
+        if rank + 1 < SIZE {
+            neighbours.push(square + SIZE);
+        }
+        if file + 1 < SIZE {
+            neighbours.push(square + 1);
+        }
+        if rank > 0 {
+            neighbours.push(square - SIZE);
+        }
+        if file > 0 {
+            neighbours.push(square - 1);
+        }
+
+        neighbours.retain(|&square| self.walls & 1 << square == 0);
+        neighbours
+    }
+
+    pub fn get_moves(&self) -> Vec<Move> {
+        self.free_neighbours(self.side_to_move)
+            .into_iter()
+            .map(|square| Move(square as u8))
+            .collect()
+    }
+
+    /// Advances the head of the side to move and passes the turn on, skipping players who are
+    /// out and knocking out those who can't move anymore.
+    pub fn make_move(&mut self, mov: Move) {
+        self.walls |= 1 << mov.square();
+        self.heads[self.side_to_move] = mov.0;
+
+        self.pass_turn();
+        self.eliminate_trapped();
+    }
+
+    fn pass_turn(&mut self) {
+        for _ in 0..PLAYERS {
+            self.side_to_move = (self.side_to_move + 1) % PLAYERS;
+
+            if self.is_alive(self.side_to_move) {
+                return;
+            }
+        }
+    }
+
+    fn eliminate_trapped(&mut self) {
+        while self.alive.count_ones() > 1 && self.free_neighbours(self.side_to_move).is_empty() {
+            self.alive &= !(1 << self.side_to_move);
+            self.pass_turn();
+        }
+    }
+
+    pub fn display(&self) -> String {
+        let mut board = String::new();
+
+        for rank in (0..SIZE).rev() {
+            board.push_str(&format!("{} ", rank + 1));
+
+            for file in 0..SIZE {
+                board.push_str(&format!(" {}", self.cell(rank * SIZE + file)));
+            }
+
+            board.push('\n');
+        }
+
+        board.push_str("   a b c d e f g h");
+        board
+    }
+}
@@ -0,0 +1,30 @@
+/// Square the head of the side to move advances to, written like `b2` with `a1` in the bottom
+/// left corner.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Move(pub u8);
+
+impl Move {
+    pub fn square(self) -> usize {
+        usize::from(self.0)
+    }
+}
+
+impl std::fmt::Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let file = char::from(b'a' + self.0 % 8);
+
+        write!(f, "{}{}", file, self.0 / 8 + 1)
+    }
+}
+
+impl From<u32> for Move {
+    fn from(mov: u32) -> Self {
+        Move(mov as u8)
+    }
+}
+
+impl From<Move> for u32 {
+    fn from(mov: Move) -> Self {
+        u32::from(mov.0)
+    }
+}
@@ -0,0 +1,92 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use mentor::Game;
+
+/// Handcrafted evaluation that is linear in its parameters, so a tuner can fit them to labelled
+/// positions.
+pub trait Tunable: Game {
+    /// Current weights of the evaluation, flattened.
+    fn parameters() -> Vec<f32>;
+
+    /// Evaluation at which the squashed value reaches `2 / (1 + e^-1) - 1`.
+    fn scale() -> f32;
+
+    /// Sparse coefficients of the parameters from the perspective of the side to move. Their dot
+    /// product with the parameters is the unsquashed evaluation.
+    fn coefficients(&self) -> Vec<(usize, f32)>;
+
+    /// Number of parameters written on a single line of the parameter file.
+    fn group() -> usize {
+        1
+    }
+}
+
+/// Reads a parameter file as written by `write_parameters`: the scale followed by `expected`
+/// weights, all separated by whitespace. Lines starting with `#` are ignored.
+pub fn read_parameters(path: &Path, expected: usize) -> io::Result<(f32, Vec<f32>)> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    let values = fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .flat_map(|line| {
+            line.split_whitespace()
+                .map(str::to_owned)
+                .collect::<Vec<_>>()
+        })
+        .map(|value| {
+            value
+                .parse::<f32>()
+                .map_err(|_| invalid(format!("Invalid parameter {:?}.", value)))
+        })
+        .collect::<io::Result<Vec<f32>>>()?;
+
+    match values.split_first() {
+        Some((&scale, weights)) if weights.len() == expected => Ok((scale, weights.to_vec())),
+        _ => Err(invalid(format!(
+            "Expected {} parameters, found {}.",
+            expected + 1,
+            values.len()
+        ))),
+    }
+}
+
+pub fn write_parameters(path: &Path, scale: f32, weights: &[f32], group: usize) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    writeln!(writer, "# scale")?;
+    writeln!(writer, "{}", scale)?;
+    writeln!(writer, "# weights")?;
+
+    for chunk in weights.chunks(group.max(1)) {
+        let line: Vec<String> = chunk.iter().map(|w| format!("{:.3}", w)).collect();
+        writeln!(writer, "{}", line.join(" "))?;
+    }
+
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tune_test {
+    use super::{read_parameters, write_parameters};
+
+    #[test]
+    fn assert_write_read_roundtrip() {
+        let path =
+            std::env::temp_dir().join(format!("mentor_tune_test_{}.params", std::process::id()));
+        let weights = [1.5, -2.25, 3.0, 0.125];
+
+        write_parameters(&path, 400.0, &weights, 2).unwrap();
+        let (scale, restored) = read_parameters(&path, weights.len()).unwrap();
+
+        assert_eq!(scale, 400.0);
+        assert_eq!(restored, weights);
+        assert!(read_parameters(&path, weights.len() + 1).is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,464 @@
+mod card;
+
+use std::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use card::Card;
+use mentor::{
+    mcts::{
+        ismcts::{InformationSetSearch, Observer},
+        params::SearchParameter,
+        Search,
+    },
+    Game, GameState,
+};
+
+use crate::{fixed_settings, mix, registry::Registered, rollout, GameProtocol};
+
+const HAND_SIZE: u32 = 5;
+
+/// Two-handed whist without trumps on a deck of jacks, queens, kings and aces. Both players get
+/// five cards and the rest stays hidden. The second card of a trick has to follow the suit of the
+/// first if it can, the higher card of that suit wins the trick and its winner leads the next.
+/// Taking more of the five tricks wins.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Whist {
+    hands: [u16; 2],
+    /// Cards of the finished tricks.
+    played: u16,
+    /// First card of the current trick.
+    led: Option<Card>,
+    tricks: [u8; 2],
+    side_to_move: usize,
+    /// Suits each player failed to follow, so that it can't hold them anymore.
+    voids: [u8; 2],
+}
+
+impl Default for Whist {
+    fn default() -> Self {
+        Self::deal(0)
+    }
+}
+
+impl Whist {
+    /// Deals the cards shuffled by `seed`. The default position is the deal of seed 0, from
+    /// which the cards of the move list notation are played.
+    pub fn deal(seed: u64) -> Self {
+        let mut deck: Vec<u8> = (0..16).collect();
+
+        for i in (1..deck.len()).rev() {
+            let j = (mix(seed ^ i as u64) % (i as u64 + 1)) as usize;
+            deck.swap(i, j);
+        }
+
+        let hand = |cards: &[u8]| cards.iter().fold(0, |hand, &card| hand | Card(card).bit());
+        let size = HAND_SIZE as usize;
+
+        Whist {
+            hands: [hand(&deck[..size]), hand(&deck[size..2 * size])],
+            played: 0,
+            led: None,
+            tricks: [0; 2],
+            side_to_move: 0,
+            voids: [0; 2],
+        }
+    }
+
+    fn suit(suit: u8) -> u16 {
+        0xF << (4 * suit)
+    }
+
+    fn cards(cards: u16) -> String {
+        match cards {
+            0 => "-".to_owned(),
+            _ => Card::all(cards).map(|card| card.to_string()).collect(),
+        }
+    }
+
+    fn parse_cards(notation: &str) -> u16 {
+        notation
+            .as_bytes()
+            .chunks(2)
+            .filter_map(|card| Card::parse(std::str::from_utf8(card).ok()?))
+            .fold(0, |cards, card| cards | card.bit())
+    }
+}
+
+impl fmt::Display for Whist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let led = self.led.map_or("-".to_owned(), |card| card.to_string());
+
+        writeln!(f, "x: {}", Self::cards(self.hands[0]))?;
+        writeln!(f, "o: {}", Self::cards(self.hands[1]))?;
+        writeln!(f, "table: {}", led)?;
+        write!(f, "tricks: {}-{}", self.tricks[0], self.tricks[1])
+    }
+}
+
+impl Game for Whist {
+    type Move = Card;
+
+    /// Accepts the hands of both players and the cards of the finished tricks, the side to move
+    /// `x` or `o`, the tricks of both and the card led, `-` without one, e.g.
+    /// `AhJh/Qs/JsQcKcAcJdQd x 1-2 Kh`, or cards played from the start position separated by
+    /// whitespace. Only the latter keeps the suits a player is known to lack. Moves after an
+    /// illegal one are ignored.
+    fn from_notation(notation: &str) -> Self {
+        let mut parts = notation.split_whitespace();
+
+        if notation.contains('/') {
+            let mut cards = parts.next().unwrap_or_default().split('/');
+            let mut next_cards = || Self::parse_cards(cards.next().unwrap_or_default());
+            let (first, second, played) = (next_cards(), next_cards(), next_cards());
+
+            let side_to_move = usize::from(parts.next() == Some("o"));
+            let tricks: Vec<u8> = parts
+                .next()
+                .unwrap_or_default()
+                .split('-')
+                .filter_map(|tricks| tricks.parse().ok())
+                .collect();
+
+            return Whist {
+                hands: [first, second],
+                played,
+                led: parts.next().and_then(Card::parse),
+                tricks: [0, 1].map(|side| tricks.get(side).copied().unwrap_or(0)),
+                side_to_move,
+                voids: [0; 2],
+            };
+        }
+
+        let mut pos = Self::default();
+
+        for notation in parts {
+            let Some(mov) = pos.parse_move(notation) else {
+                break;
+            };
+
+            pos.make_move(mov);
+        }
+
+        pos
+    }
+
+    fn notation(&self) -> String {
+        let side = if self.side_to_move == 0 { 'x' } else { 'o' };
+        let led = self.led.map_or("-".to_owned(), |card| card.to_string());
+
+        format!(
+            "{}/{}/{} {} {}-{} {}",
+            Self::cards(self.hands[0]),
+            Self::cards(self.hands[1]),
+            Self::cards(self.played),
+            side,
+            self.tricks[0],
+            self.tricks[1],
+            led
+        )
+    }
+
+    fn side_to_move(&self) -> usize {
+        self.side_to_move
+    }
+
+    fn game_state(&self) -> GameState {
+        if self.hands[self.side_to_move] != 0 {
+            return GameState::Ongoing;
+        }
+
+        let tricks = self.tricks[self.side_to_move];
+        let opponent = self.tricks[self.side_to_move ^ 1];
+
+        match tricks.cmp(&opponent) {
+            std::cmp::Ordering::Greater => GameState::Win,
+            std::cmp::Ordering::Equal => GameState::Draw,
+            std::cmp::Ordering::Less => GameState::Loss,
+        }
+    }
+
+    fn hash(&self) -> u64 {
+        let cards = u64::from(self.hands[0])
+            | u64::from(self.hands[1]) << 16
+            | u64::from(self.played) << 32
+            | u64::from(self.led.map_or(0xFF, |card| card.0)) << 48;
+        let state = u64::from(self.tricks[0])
+            | u64::from(self.tricks[1]) << 4
+            | u64::from(self.voids[0]) << 8
+            | u64::from(self.voids[1]) << 12
+            | (self.side_to_move as u64) << 16;
+
+        mix(cards ^ mix(state))
+    }
+
+    /// Plays random cards to the end, which sees every hand, so searches should only run on
+    /// determinizations.
+    fn get_value(&mut self) -> f32 {
+        let (pos, _) = rollout(self, usize::MAX);
+
+        let reward = match pos.game_state() {
+            GameState::Win => 1.0,
+            GameState::Loss => -1.0,
+            _ => 0.0,
+        };
+
+        if self.side_to_move() == pos.side_to_move() {
+            reward
+        } else {
+            -reward
+        }
+    }
+
+    fn get_policy(&mut self, moves: &[Self::Move]) -> Vec<f32> {
+        vec![1.0 / moves.len() as f32; moves.len()]
+    }
+
+    fn make_move(&mut self, mov: Self::Move) {
+        let side = self.side_to_move;
+        self.hands[side] &= !mov.bit();
+
+        let Some(led) = self.led else {
+            self.led = Some(mov);
+            self.side_to_move ^= 1;
+            return;
+        };
+
+        if mov.suit() != led.suit() {
+            self.voids[side] |= 1 << led.suit();
+        }
+
+        let winner = if mov.suit() == led.suit() && mov.rank() > led.rank() {
+            side
+        } else {
+            side ^ 1
+        };
+
+        self.tricks[winner] += 1;
+        self.played |= led.bit() | mov.bit();
+        self.led = None;
+        self.side_to_move = winner;
+    }
+
+    fn get_legal_moves(&self) -> Vec<Self::Move> {
+        let hand = self.hands[self.side_to_move];
+
+        let follow = self.led.map_or(0, |led| hand & Self::suit(led.suit()));
+        let cards = if follow != 0 { follow } else { hand };
+
+        Card::all(cards).collect()
+    }
+
+    /// Deals the opponent of `observer` as many cards as it holds from those `observer` hasn't
+    /// seen, leaving out the suits it is known to lack.
+    fn determinize(&self, observer: usize, seed: u64) -> Self {
+        let opponent = observer ^ 1;
+        let led = self.led.map_or(0, Card::bit);
+
+        let mut unseen = !(self.hands[observer] | self.played | led);
+        for suit in 0..4 {
+            if self.voids[opponent] & 1 << suit != 0 {
+                unseen &= !Self::suit(suit);
+            }
+        }
+
+        let mut pos = *self;
+        pos.hands[opponent] = 0;
+
+        for i in 0..self.hands[opponent].count_ones() {
+            let index = mix(seed.wrapping_add(u64::from(i))) % u64::from(unseen.count_ones());
+            let card = Card::all(unseen).nth(index as usize).unwrap();
+
+            pos.hands[opponent] |= card.bit();
+            unseen &= !card.bit();
+        }
+
+        pos
+    }
+}
+
+impl Registered for Whist {
+    type Protocol = WhistProtocol;
+
+    const NAME: &'static str = "whist";
+
+    fn start(seed: u64) -> Self {
+        Self::deal(seed)
+    }
+}
+
+#[derive(Default)]
+pub struct WhistProtocol {
+    observer: Observer,
+    /// Deal of the current game, which is random from the first new game on.
+    start: Whist,
+    /// Seed of every deal if set by the option `Seed`, otherwise they are random.
+    seed: Option<u64>,
+}
+
+impl GameProtocol for WhistProtocol {
+    type Game = Whist;
+
+    const NAME: &'static str = "uci";
+    const NEW_GAME: &'static str = "newgame";
+    const NOTATION: &'static str = "layout";
+
+    const DEFAULT_POS: String = String::new();
+
+    fn options(&mut self) {
+        println!("option name Observer type combo default single var single var multiple");
+        println!("option name Seed type string default <random>");
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) {
+        match name {
+            "Observer" => {
+                self.observer = match value {
+                    "multiple" => Observer::Multiple,
+                    _ => Observer::Single,
+                };
+            }
+            "Seed" => {
+                self.seed = value.parse().ok();
+                self.new_game();
+            }
+            _ => {}
+        }
+    }
+
+    fn start_pos(&self) -> Whist {
+        self.start
+    }
+
+    fn new_game(&mut self) {
+        self.start = Whist::deal(self.seed.unwrap_or_else(rand::random));
+    }
+
+    /// Searches the information sets of the side to move instead of the position, which shows
+    /// the cards of the opponent.
+    fn go(
+        &mut self,
+        pos: &mut Self::Game,
+        _: &mut Search<Self::Game>,
+        params: &SearchParameter,
+        commands: Vec<&str>,
+    ) {
+        let settings = fixed_settings(&commands, 5_000);
+        let mut search = InformationSetSearch::new(*pos, 50_000, self.observer);
+        let abort = AtomicBool::new(false);
+
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                let mov = search.run(None, &settings, params, &abort);
+                println!("bestmove {}", mov);
+
+                abort.store(true, Ordering::Relaxed);
+            });
+
+            self.search_input(&abort);
+        });
+    }
+}
+
+#[cfg(test)]
+mod whist_test {
+    use super::{card::Card, Whist, WhistProtocol};
+    use crate::GameProtocol;
+    use mentor::{
+        mcts::{
+            ismcts::{InformationSetSearch, Observer},
+            params::SearchParameter,
+            settings::SearchSettings,
+        },
+        Game, GameState,
+    };
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn assert_tricks() {
+        let mut pos = Whist::from_notation("AhJhKs/QsJcAd/- x 0-0 -");
+        pos.make_move(pos.parse_move("Ah").unwrap());
+
+        // Without a heart any card may follow, and the trick goes to the leader.
+        assert_eq!(pos.get_legal_moves().len(), 3);
+        pos.make_move(pos.parse_move("Jc").unwrap());
+        assert_eq!(pos.notation(), "JhKs/AdQs/JcAh x 1-0 -");
+
+        // The opponent can't hold a heart anymore, and the cards it could hold are hidden.
+        for seed in 0..100 {
+            let determinization = pos.determinize(0, seed);
+            let hand = determinization.hands[1];
+
+            assert_eq!(determinization.hands[0], pos.hands[0]);
+            assert_eq!(hand.count_ones(), 2);
+            assert_eq!(hand & (Whist::suit(2) | pos.played | pos.hands[0]), 0);
+        }
+
+        let mut pos = Whist::from_notation("Ks/-/- x 2-2 As");
+        pos.make_move(Card::parse("Ks").unwrap());
+        assert_eq!(pos.side_to_move(), 1);
+        assert_eq!(pos.game_state(), GameState::Win);
+    }
+
+    #[test]
+    fn assert_deal() {
+        let pos = Whist::deal(7);
+        assert!(pos == Whist::deal(7));
+        assert_eq!(pos.hands.map(u16::count_ones), [5, 5]);
+        assert_eq!(pos.hands[0] & pos.hands[1], 0);
+
+        // Cards played from the start position apply to the deal of seed 0.
+        assert!(Whist::default() == Whist::deal(0));
+        assert!(pos != Whist::deal(8));
+
+        let mut start = Whist::deal(0);
+        let mov = start.get_legal_moves()[0];
+        start.make_move(mov);
+        assert!(Whist::from_notation(&mov.to_string()) == start);
+
+        // The protocol keeps the deal of the game, so that moves from the start position apply
+        // to the same cards.
+        let mut protocol = WhistProtocol::default();
+        protocol.set_option("Seed", "7");
+        assert!(protocol.start_pos() == pos);
+
+        let mov = pos.get_legal_moves()[0].to_string();
+        let mut played = Whist::default();
+        let history = protocol
+            .position(&mut played, vec!["position", "startpos", "moves", &mov])
+            .unwrap();
+
+        assert_eq!(history, [pos.hash()]);
+        assert_eq!(
+            played.hands[0],
+            pos.hands[0] & !Card::parse(&mov).unwrap().bit()
+        );
+    }
+
+    #[test]
+    fn assert_information_set_search() {
+        // Only the ace wins the trick, and the jack still takes the last one unless the hidden
+        // card is the queen of hearts.
+        let pos = Whist::from_notation("AhJh/Qs/JsQcKcAcJdQd x 1-2 Kh");
+
+        for observer in [Observer::Single, Observer::Multiple] {
+            let settings = SearchSettings {
+                max_time: None,
+                max_nodes: 2_000,
+                batch_size: 1,
+            };
+
+            let mut search = InformationSetSearch::new(pos, 2_000, observer);
+            let mov = search.run(
+                None,
+                &settings,
+                &SearchParameter::default(),
+                &AtomicBool::new(false),
+            );
+
+            assert_eq!(mov.to_string(), "Ah");
+            assert!(search.root_q() > 0.0);
+        }
+    }
+}
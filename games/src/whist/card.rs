@@ -0,0 +1,64 @@
+const RANKS: &[u8; 4] = b"JQKA";
+const SUITS: &[u8; 4] = b"cdhs";
+
+/// Card of the 16 card deck, numbered by suit and then by rank, written like `Ah`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Card(pub u8);
+
+impl Card {
+    pub fn suit(self) -> u8 {
+        self.0 / 4
+    }
+
+    pub fn rank(self) -> u8 {
+        self.0 % 4
+    }
+
+    pub fn bit(self) -> u16 {
+        1 << self.0
+    }
+
+    pub fn parse(notation: &str) -> Option<Self> {
+        let &[rank, suit] = notation.as_bytes() else {
+            return None;
+        };
+
+        let rank = RANKS.iter().position(|&c| c == rank)?;
+        let suit = SUITS.iter().position(|&c| c == suit)?;
+
+        Some(Card((suit * 4 + rank) as u8))
+    }
+
+    /// Cards of a set, lowest first.
+    pub fn all(mut cards: u16) -> impl Iterator<Item = Card> {
+        std::iter::from_fn(move || {
+            (cards != 0).then(|| {
+                let card = Card(cards.trailing_zeros() as u8);
+                cards &= cards - 1;
+
+                card
+            })
+        })
+    }
+}
+
+impl std::fmt::Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rank = char::from(RANKS[usize::from(self.rank())]);
+        let suit = char::from(SUITS[usize::from(self.suit())]);
+
+        write!(f, "{}{}", rank, suit)
+    }
+}
+
+impl From<u32> for Card {
+    fn from(mov: u32) -> Self {
+        Card(mov as u8)
+    }
+}
+
+impl From<Card> for u32 {
+    fn from(card: Card) -> Self {
+        u32::from(card.0)
+    }
+}
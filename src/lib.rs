@@ -2,6 +2,12 @@ pub mod mcts;
 pub mod network;
 mod tree;
 
+/// Most players a game may have.
+pub const MAX_PLAYERS: usize = 4;
+
+/// Value of a position for every player, each between -1 and 1.
+pub type Rewards = [f32; MAX_PLAYERS];
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
 pub enum GameState {
     #[default]
@@ -9,24 +15,141 @@ pub enum GameState {
     Win,
     Draw,
     Loss,
+    /// Result of every player, 1 for a win, 0 for a draw and -1 for a loss, for games where the
+    /// result of the side to move doesn't determine the others.
+    Outcome([i8; MAX_PLAYERS]),
+}
+
+impl GameState {
+    /// Rewards of the players of a finished game. `Win` and `Loss` are from the perspective of
+    /// the side to move, and every other player gets the opposite.
+    pub fn rewards(self, side_to_move: usize) -> Rewards {
+        let utility = match self {
+            GameState::Ongoing => unreachable!(),
+            GameState::Outcome(results) => return results.map(f32::from),
+            GameState::Win => 1.0,
+            GameState::Draw => 0.0,
+            GameState::Loss => -1.0,
+        };
+
+        Self::spread(utility, side_to_move)
+    }
+
+    /// Rewards where the side to move gets `value` and every other player its negation.
+    pub fn spread(value: f32, side_to_move: usize) -> Rewards {
+        let mut rewards = [-value; MAX_PLAYERS];
+        rewards[side_to_move] = value;
+
+        rewards
+    }
 }
 
-pub trait Game: Clone + Copy + Default + Eq + std::fmt::Display {
-    type Move: std::fmt::Display + From<u16> + Into<u16> + Copy;
+pub trait Game: Clone + Default + Eq + std::fmt::Display {
+    /// Moves are stored in the tree as `u32`, which leaves room for sequences such as
+    /// multi-jump captures.
+    type Move: std::fmt::Display + From<u32> + Into<u32> + Copy;
+
+    /// Number of players, at most `MAX_PLAYERS`, which take turns as `side_to_move` says.
+    const PLAYERS: usize = 2;
+
+    /// Occurrences of a position which draw the game, e.g. 3 for threefold repetition in chess, or
+    /// 0 if repetitions don't end the game.
+    const REPETITIONS: usize = 0;
 
     fn from_notation(notation: &str) -> Self;
 
+    /// Textual form of the position which `from_notation` accepts.
+    fn notation(&self) -> String;
+
     fn side_to_move(&self) -> usize;
 
     fn game_state(&self) -> GameState;
 
     fn hash(&self) -> u64;
 
+    /// Whether the position is drawn by repetition, given the hashes of the positions before it
+    /// since the start of the game. Unlike `game_state` this depends on how the position was
+    /// reached, so the search checks it along every path instead of storing it in the tree.
+    fn is_repetition(&self, history: &[u64]) -> bool {
+        let hash = self.hash();
+
+        Self::REPETITIONS > 0
+            && history.iter().filter(|&&previous| previous == hash).count() + 1 >= Self::REPETITIONS
+    }
+
+    /// Value of the position for the side to move. It may cache what it computes, but has to
+    /// leave the position unchanged, as a search with `UnmakeMove` evaluates the position it walks.
     fn get_value(&mut self) -> f32;
 
+    /// Evaluates a batch of leaf positions collected by the search. Games backed by a
+    /// network should override this to run a single batched forward pass.
+    fn get_values(positions: &mut [Self]) -> Vec<f32> {
+        positions.iter_mut().map(|pos| pos.get_value()).collect()
+    }
+
+    /// Evaluates a batch of leaf positions for every player. By default the side to move gets
+    /// the value of `get_values` and every other player its negation, which games with more
+    /// than two players should override.
+    fn get_rewards(positions: &mut [Self]) -> Vec<Rewards> {
+        let values = Self::get_values(positions);
+
+        positions
+            .iter()
+            .zip(values)
+            .map(|(pos, value)| GameState::spread(value, pos.side_to_move()))
+            .collect()
+    }
+
+    /// Prior probabilities of the legal `moves`. Like `get_value` it has to leave the position
+    /// unchanged.
     fn get_policy(&mut self, moves: &[Self::Move]) -> Vec<f32>;
 
     fn make_move(&mut self, mov: Self::Move);
 
+    /// Legal move written in the notation of the game, or `None` if it is unparseable or illegal.
+    /// By default the notation is matched against the `Display` form of the legal moves.
+    fn parse_move(&self, notation: &str) -> Option<Self::Move> {
+        self.get_legal_moves()
+            .into_iter()
+            .find(|mov| mov.to_string() == notation)
+    }
+
     fn get_legal_moves(&self) -> Vec<Self::Move>;
+
+    /// Whether the next transition is random, e.g. a roll of dice, instead of a choice of the side
+    /// to move.
+    fn is_chance(&self) -> bool {
+        false
+    }
+
+    /// Possible outcomes of a chance position with their probabilities, which are played with
+    /// `make_move`.
+    fn get_outcomes(&self) -> Vec<(Self::Move, f32)> {
+        Vec::new()
+    }
+
+    /// Position with the information hidden from `observer`, e.g. the cards of the other players,
+    /// drawn at random from `seed` consistently with everything it has seen. Games of perfect
+    /// information return the position unchanged.
+    fn determinize(&self, _observer: usize, _seed: u64) -> Self {
+        self.clone()
+    }
+
+    /// Move of the side to move as `observer` sees it, e.g. only the back of a card played face
+    /// down. The side to move sees its own moves unchanged, as does everyone in games where every
+    /// move is public.
+    fn observe(&self, mov: Self::Move, _observer: usize) -> Self::Move {
+        mov
+    }
+}
+
+/// Games which can take back moves, so that a search created with `Search::with_unmake` walks a
+/// single position down the tree and back up instead of copying the root for every leaf.
+pub trait UnmakeMove: Game {
+    /// Starts or stops keeping what is needed to take back moves, which the search only asks for
+    /// while it walks the position. Stopping may drop what was kept.
+    fn record_moves(&mut self, record: bool);
+
+    /// Takes back `mov`, which was the last move made while recording.
+    fn unmake_move(&mut self, mov: Self::Move);
 }
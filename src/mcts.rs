@@ -1,7 +1,8 @@
+pub mod ismcts;
 pub mod params;
 pub mod settings;
 
-use params::SearchParameter;
+use params::{SearchParameter, Strategy};
 use settings::SearchSettings;
 
 use std::{
@@ -9,11 +10,22 @@ use std::{
     time::Instant,
 };
 
-use crate::{tree::Tree, Game, GameState};
+use crate::{tree::Tree, Game, GameState, Rewards, UnmakeMove};
+
+/// `UnmakeMove::record_moves` and `UnmakeMove::unmake_move`, kept as pointers so that the
+/// search needs no such bound.
+type Unmake<G> = (fn(&mut G, bool), fn(&mut G, <G as Game>::Move));
 
 pub struct Search<G: Game> {
     root: G,
     tree: Tree,
+    /// State of the xorshift generator sampling the outcomes of chance nodes.
+    seed: u64,
+    /// Hashes of the positions since the start of the game up to the root, followed by those
+    /// of the path while a leaf is selected.
+    history: Vec<u64>,
+    /// Takes back moves if the search was created with `with_unmake`.
+    unmake: Option<Unmake<G>>,
 }
 
 impl<G: Game> Search<G> {
@@ -21,9 +33,18 @@ impl<G: Game> Search<G> {
         Search {
             root: pos,
             tree: Tree::new(capacity),
+            seed: 0x9E37_79B9_7F4A_7C15,
+            history: Vec::new(),
+            unmake: None,
         }
     }
 
+    /// Sets the hashes of the positions played before the root since the start of the game, so
+    /// that positions of the search repeating them are drawn.
+    pub fn set_history(&mut self, history: Vec<u64>) {
+        self.history = history;
+    }
+
     pub fn run(
         &mut self,
         pos: Option<G>,
@@ -39,16 +60,15 @@ impl<G: Game> Search<G> {
             self.root = pos;
         }
 
+        let player = self.root.side_to_move();
+
         let mut nodes = 0;
         loop {
-            let mut root = self.root;
-
-            self.execute_iteration(self.tree.root(), &mut root, params);
+            nodes += self.execute_batch(setings.batch_size, params);
             if self.tree[self.tree.root()].is_terminal() {
                 break;
             }
 
-            nodes += 1;
             if nodes >= setings.max_nodes {
                 break;
             }
@@ -76,8 +96,8 @@ impl<G: Game> Search<G> {
                         "move {} n {} w {} q {}",
                         edge.mov(),
                         self.tree[edge.ptr()].visits(),
-                        self.tree[edge.ptr()].value(),
-                        self.tree[edge.ptr()].q()
+                        self.tree[edge.ptr()].value(player),
+                        self.tree[edge.ptr()].q(player)
                     );
                 }
 
@@ -89,62 +109,261 @@ impl<G: Game> Search<G> {
             .into()
     }
 
-    pub fn execute_iteration(&mut self, index: i32, pos: &mut G, params: &SearchParameter) -> f32 {
-        let reward = if self.tree[index].visits() == 0.0 || self.tree[index].is_terminal() {
-            self.get_utility(index, pos)
-        } else {
-            if self.tree[index].is_not_expanded() {
+    /// Average value of the root from the perspective of its side to move.
+    pub fn root_q(&self) -> f32 {
+        self.tree[self.tree.root()].q(self.root.side_to_move())
+    }
+
+    pub fn execute_batch(&mut self, batch_size: usize, params: &SearchParameter) -> usize {
+        if let Some(unmake) = self.unmake {
+            return self.execute_unmake(unmake, batch_size, params);
+        }
+
+        let mut paths = Vec::with_capacity(batch_size);
+        let mut leaves = Vec::with_capacity(batch_size);
+
+        while paths.len() < batch_size.max(1) {
+            let mut pos = self.root.clone();
+
+            let Some((path, rewards)) = self.select_leaf(&mut pos, &mut Vec::new(), params) else {
+                break;
+            };
+
+            if rewards.is_none() {
+                leaves.push(pos);
+            }
+
+            paths.push((path, rewards));
+
+            if self.tree[self.tree.root()].is_terminal() {
+                break;
+            }
+        }
+
+        self.backpropagate_batch(&paths, &mut leaves);
+
+        paths.len()
+    }
+
+    /// Walks the root itself down to every leaf and back up with `unmake_move`, recording the
+    /// moves only meanwhile. Only the leaves of a batch larger than one are copied, so that they
+    /// can be evaluated together.
+    fn execute_unmake(
+        &mut self,
+        (record, unmake): Unmake<G>,
+        batch_size: usize,
+        params: &SearchParameter,
+    ) -> usize {
+        let mut pos = std::mem::take(&mut self.root);
+        record(&mut pos, true);
+
+        let mut moves = Vec::new();
+        let mut paths = Vec::with_capacity(batch_size);
+        let mut leaves = Vec::with_capacity(batch_size);
+
+        while paths.len() < batch_size.max(1) {
+            let leaf = self.select_leaf(&mut pos, &mut moves, params);
+            let selected = leaf.is_some();
+
+            if let Some((path, mut rewards)) = leaf {
+                if rewards.is_none() && batch_size <= 1 {
+                    rewards = Some(G::get_rewards(std::slice::from_mut(&mut pos))[0]);
+                } else if rewards.is_none() {
+                    let mut leaf = pos.clone();
+                    record(&mut leaf, false);
+                    leaves.push(leaf);
+                }
+
+                paths.push((path, rewards));
+            }
+
+            for mov in moves.drain(..).rev() {
+                unmake(&mut pos, mov);
+            }
+
+            if !selected || self.tree[self.tree.root()].is_terminal() {
+                break;
+            }
+        }
+
+        record(&mut pos, false);
+        self.root = pos;
+        self.backpropagate_batch(&paths, &mut leaves);
+
+        paths.len()
+    }
+
+    /// Evaluates the leaves together and backs up every path of the batch, in the order they
+    /// were selected, with the rewards of its leaf.
+    fn backpropagate_batch(&mut self, paths: &[(Vec<i32>, Option<Rewards>)], leaves: &mut [G]) {
+        let mut values = G::get_rewards(leaves).into_iter();
+        for (path, rewards) in paths {
+            let rewards = rewards.unwrap_or_else(|| values.next().unwrap());
+            self.backpropagate(path, &rewards);
+        }
+    }
+
+    /// Descends from the root to a leaf, playing the moves on `pos` and recording them in `moves`.
+    /// Returns the path along with the rewards of the leaf if they are known without evaluation,
+    /// which is the case at the end of the game or on a repetition.
+    fn select_leaf(
+        &mut self,
+        pos: &mut G,
+        moves: &mut Vec<G::Move>,
+        params: &SearchParameter,
+    ) -> Option<(Vec<i32>, Option<Rewards>)> {
+        let mut index = self.tree.root();
+        let mut path = vec![index];
+
+        let start = self.history.len();
+        let mut repetition = false;
+
+        loop {
+            let node = &self.tree[index];
+            if node.is_terminal() {
+                break;
+            }
+
+            if node.visits() == 0.0 {
+                // The leaf is already awaiting evaluation in the current batch.
+                if node.virtual_loss() > 0.0 {
+                    self.history.truncate(start);
+                    return None;
+                }
+
+                break;
+            }
+
+            if node.is_not_expanded() {
                 self.tree[index].expand(pos);
             }
 
-            let action = self.pick_action(index, params);
+            let action = if self.tree[index].is_chance() {
+                self.sample_outcome(index)
+            } else {
+                self.pick_action(index, params)
+            };
             let edge = self.tree.edge(index, action);
 
             let mut edge_ptr = edge.ptr();
 
-            pos.make_move(edge.mov().into());
+            let mov = edge.mov().into();
+            self.history.push(pos.hash());
+            pos.make_move(mov);
+            moves.push(mov);
 
             if edge.ptr() == -1 {
-                edge_ptr = self.tree.add(pos.game_state(), pos.hash(), index);
+                edge_ptr = self.tree.add(pos, pos.game_state(), index);
                 self.tree.edge_mut(index, action).set_ptr(edge_ptr);
             }
 
-            self.execute_iteration(edge_ptr, pos, params)
+            index = edge_ptr;
+            path.push(index);
+
+            // A repetition depends on the path, so the node is scored as a draw without storing
+            // it as terminal, which it isn't when reached from another root.
+            if !self.tree[index].is_terminal() && pos.is_repetition(&self.history) {
+                repetition = true;
+                break;
+            }
+        }
+
+        self.history.truncate(start);
+
+        for &index in &path {
+            self.tree[index].add_virtual_loss();
+        }
+
+        let node = &self.tree[index];
+        let rewards = if node.is_terminal() {
+            Some(node.game_state().rewards(node.side_to_move()))
+        } else if repetition {
+            Some(GameState::Draw.rewards(node.side_to_move()))
+        } else {
+            None
         };
 
-        let reward = -reward;
-        self.tree.propagate(index, reward);
+        Some((path, rewards))
+    }
+
+    /// Propagates the rewards of the leaf up the path. They hold the reward of every player, so
+    /// each node is read from the perspective of whoever chooses it.
+    fn backpropagate(&mut self, path: &[i32], rewards: &Rewards) {
+        for &index in path.iter().rev() {
+            self.tree[index].remove_virtual_loss();
+            self.tree.propagate(index, rewards);
+        }
+    }
+
+    /// Draws an outcome of a chance node according to the probabilities stored as its policy.
+    fn sample_outcome(&mut self, index: i32) -> usize {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+
+        let actions = self.tree[index].actions();
+        let total: f32 = actions.iter().map(|edge| edge.policy()).sum();
+        let mut sample = (self.seed >> 40) as f32 / (1u64 << 24) as f32 * total;
+
+        for (i, edge) in actions.iter().enumerate() {
+            sample -= edge.policy();
+            if sample < 0.0 {
+                return i;
+            }
+        }
 
-        reward
+        actions.len() - 1
     }
 
     fn pick_action(&mut self, index: i32, params: &SearchParameter) -> usize {
         let node = &self.tree[index];
 
-        let expl = params.cpuct(node) * node.visits().sqrt();
+        let expl = params.cpuct(node) * node.virtual_visits().sqrt();
+
+        // With the paranoid strategy the opponents of the root player minimise its reward.
+        let player = node.side_to_move();
+        let root = self.root.side_to_move();
+        let (perspective, sign) = match params.strategy {
+            Strategy::Paranoid if player != root => (root, -1.0),
+            _ => (player, 1.0),
+        };
 
         let mut best = 0;
         let mut max = f32::NEG_INFINITY;
 
-        let mut proven_win = true;
+        // `Win` and `Loss` only decide the result of the parent between two players.
+        let proving = G::PLAYERS == 2;
+        let mut proven_loss = proving;
 
         for (i, action) in node.actions().iter().enumerate() {
             if action.ptr() == -1 {
                 return i;
             }
 
-            let state = self.tree[action.ptr()].game_state();
-            if state == GameState::Loss {
+            let child = &self.tree[action.ptr()];
+
+            // The state of a child is from the perspective of its side to move, which is still the
+            // same player after an extra turn.
+            let state = match child.game_state() {
+                state if child.side_to_move() == player => state,
+                GameState::Win => GameState::Loss,
+                GameState::Loss => GameState::Win,
+                state => state,
+            };
+
+            if proving && state == GameState::Win {
                 self.tree[index].set_game_state(GameState::Win);
                 return i;
             }
 
-            if state != GameState::Win {
-                proven_win = false;
+            if state != GameState::Loss {
+                proven_loss = false;
             }
 
-            let u = expl * action.policy() / (1.0 + self.tree[action.ptr()].visits());
-            let uct = self.tree[action.ptr()].q() + u;
+            let u = expl * action.policy() / (1.0 + child.virtual_visits());
+            let q =
+                (sign * child.value(perspective) - child.virtual_loss()) / child.virtual_visits();
+            let uct = q + u;
 
             if max < uct {
                 best = i;
@@ -152,19 +371,321 @@ impl<G: Game> Search<G> {
             }
         }
 
-        if proven_win {
+        if proven_loss {
             self.tree[index].set_game_state(GameState::Loss);
         }
 
         best
     }
+}
+
+impl<G: UnmakeMove> Search<G> {
+    /// Search which plays the moves to every leaf on the root itself and takes them back
+    /// afterwards.
+    pub fn with_unmake(pos: G, capacity: usize) -> Self {
+        Search {
+            unmake: Some((G::record_moves, G::unmake_move)),
+            ..Self::new(pos, capacity)
+        }
+    }
+}
+
+#[cfg(test)]
+mod mcts_test {
+    use super::{params::SearchParameter, settings::SearchSettings, Search};
+    use crate::{Game, GameState, UnmakeMove};
+    use std::{fmt, sync::atomic::AtomicBool};
+
+    /// Takes one or two stones from a pile, where taking one grants an extra turn and whoever
+    /// takes the last stone loses. The history of turns makes it a state on the heap, which is
+    /// searched either by copying or with `unmake_move`.
+    #[derive(Clone, Default, PartialEq, Eq)]
+    struct ExtraTurn {
+        stones: u8,
+        side_to_move: usize,
+        last_mover: usize,
+        history: Vec<(usize, usize)>,
+    }
+
+    impl fmt::Display for ExtraTurn {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{} {}", self.stones, self.side_to_move)
+        }
+    }
+
+    impl Game for ExtraTurn {
+        type Move = u32;
 
-    fn get_utility(&self, index: i32, pos: &mut G) -> f32 {
-        match self.tree[index].game_state() {
-            GameState::Ongoing => pos.get_value(),
-            GameState::Win => 1.0,
-            GameState::Draw => 0.0,
-            GameState::Loss => -1.0,
+        fn from_notation(notation: &str) -> Self {
+            ExtraTurn {
+                stones: notation.parse().unwrap(),
+                ..Self::default()
+            }
+        }
+
+        fn notation(&self) -> String {
+            self.to_string()
         }
+
+        fn side_to_move(&self) -> usize {
+            self.side_to_move
+        }
+
+        fn game_state(&self) -> GameState {
+            match self.stones {
+                0 if self.last_mover == self.side_to_move => GameState::Loss,
+                0 => GameState::Win,
+                _ => GameState::Ongoing,
+            }
+        }
+
+        fn hash(&self) -> u64 {
+            u64::from(self.stones) << 2 | (self.side_to_move << 1 | self.last_mover) as u64
+        }
+
+        fn get_value(&mut self) -> f32 {
+            0.0
+        }
+
+        fn get_policy(&mut self, moves: &[Self::Move]) -> Vec<f32> {
+            vec![1.0 / moves.len() as f32; moves.len()]
+        }
+
+        fn make_move(&mut self, mov: Self::Move) {
+            self.history.push((self.side_to_move, self.last_mover));
+            self.stones -= mov as u8;
+            self.last_mover = self.side_to_move;
+
+            if mov == 2 {
+                self.side_to_move ^= 1;
+            }
+        }
+
+        fn get_legal_moves(&self) -> Vec<Self::Move> {
+            (1..=2)
+                .filter(|&mov| mov <= u32::from(self.stones))
+                .collect()
+        }
+    }
+
+    impl UnmakeMove for ExtraTurn {
+        /// The history of turns is part of the position, so it is always kept.
+        fn record_moves(&mut self, _: bool) {}
+
+        fn unmake_move(&mut self, mov: Self::Move) {
+            self.stones += mov as u8;
+            (self.side_to_move, self.last_mover) = self.history.pop().unwrap();
+        }
+    }
+
+    /// Passes the turn back and forth until a player resigns, which draws by the second
+    /// occurrence of a position.
+    #[derive(Clone, Default, PartialEq, Eq)]
+    struct Shuttle {
+        side_to_move: usize,
+        resigned: bool,
+    }
+
+    impl fmt::Display for Shuttle {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{} {}", self.side_to_move, self.resigned)
+        }
+    }
+
+    impl Game for Shuttle {
+        type Move = u32;
+
+        const REPETITIONS: usize = 2;
+
+        fn from_notation(_notation: &str) -> Self {
+            Self::default()
+        }
+
+        fn notation(&self) -> String {
+            self.to_string()
+        }
+
+        fn side_to_move(&self) -> usize {
+            self.side_to_move
+        }
+
+        fn game_state(&self) -> GameState {
+            if self.resigned {
+                GameState::Win
+            } else {
+                GameState::Ongoing
+            }
+        }
+
+        fn hash(&self) -> u64 {
+            (usize::from(self.resigned) << 1 | self.side_to_move) as u64
+        }
+
+        fn get_value(&mut self) -> f32 {
+            0.5
+        }
+
+        fn get_policy(&mut self, moves: &[Self::Move]) -> Vec<f32> {
+            vec![1.0 / moves.len() as f32; moves.len()]
+        }
+
+        fn make_move(&mut self, mov: Self::Move) {
+            self.resigned = mov == 1;
+            self.side_to_move ^= 1;
+        }
+
+        fn get_legal_moves(&self) -> Vec<Self::Move> {
+            vec![0, 1]
+        }
+    }
+
+    fn run(stones: &str, unmake: bool) -> (Search<ExtraTurn>, u32) {
+        let pos = ExtraTurn::from_notation(stones);
+        let settings = SearchSettings {
+            max_time: None,
+            max_nodes: 1_000,
+            batch_size: 1,
+        };
+
+        let mut search = if unmake {
+            Search::with_unmake(pos.clone(), 1_000)
+        } else {
+            Search::new(pos.clone(), 1_000)
+        };
+        let mov = search.run(
+            Some(pos),
+            &settings,
+            &SearchParameter::default(),
+            &AtomicBool::new(false),
+            false,
+        );
+
+        (search, mov)
+    }
+
+    #[test]
+    fn assert_extra_turn_proof() {
+        // Taking one stone leaves the same player with two, which loses either way.
+        let (search, mov) = run("3", false);
+        assert_eq!(mov, 2);
+        assert_eq!(search.tree[search.tree.root()].game_state(), GameState::Win);
+
+        // The last stone is taken with an extra turn, so the player who took it is still to move.
+        for stones in ["1", "2"] {
+            let (search, _) = run(stones, false);
+            let root = &search.tree[search.tree.root()];
+
+            assert_eq!(root.game_state(), GameState::Loss);
+        }
+    }
+
+    #[test]
+    fn assert_extra_turn_values() {
+        let (search, _) = run("5", false);
+        let root = &search.tree[search.tree.root()];
+
+        // Only taking one stone wins, which the values of both children show from the perspective
+        // of the root player, whoever moves next.
+        for edge in root.actions() {
+            let child = &search.tree[edge.ptr()];
+            let mut pos = ExtraTurn::from_notation("5");
+            pos.make_move(edge.mov());
+
+            assert_eq!(child.side_to_move(), pos.side_to_move());
+            assert_eq!(child.q(0) > 0.0, edge.mov() == 1);
+        }
+    }
+
+    #[test]
+    fn assert_unmake_search() {
+        // Both paths take the same decisions, so they have to grow the same tree.
+        for stones in ["2", "5", "8"] {
+            let (copied, copied_mov) = run(stones, false);
+            let (unmade, unmade_mov) = run(stones, true);
+
+            assert_eq!(copied_mov, unmade_mov);
+            assert_eq!(copied.tree.len(), unmade.tree.len());
+            assert_eq!(copied.root_q(), unmade.root_q());
+            assert_eq!(unmade.root.history, []);
+        }
+    }
+
+    #[test]
+    fn assert_batch_balance() {
+        let params = SearchParameter::default();
+
+        for unmake in [false, true] {
+            let pos = ExtraTurn::from_notation("8");
+            let mut search = if unmake {
+                Search::with_unmake(pos.clone(), 1_000)
+            } else {
+                Search::new(pos.clone(), 1_000)
+            };
+            search.tree.reset(&pos);
+
+            let mut nodes = 0;
+            let mut collided = false;
+            for _ in 0..50 {
+                // A batch ends early once it selects a leaf already awaiting evaluation, which
+                // is the case for the unvisited root at once.
+                let count = search.execute_batch(8, &params);
+                collided |= count < 8;
+                nodes += count;
+
+                // Every path of the batch is backed up, which takes back all of its virtual loss.
+                for index in 0..search.tree.len() {
+                    assert_eq!(search.tree[index].virtual_loss(), 0.0);
+                }
+                assert_eq!(search.tree[search.tree.root()].visits(), nodes as f32);
+            }
+
+            assert!(collided);
+            assert!(nodes > 50);
+            assert_eq!(search.root.history, []);
+        }
+    }
+
+    #[test]
+    fn assert_repetition_draw() {
+        let pos = Shuttle::default();
+        let settings = SearchSettings {
+            max_time: None,
+            max_nodes: 200,
+            batch_size: 1,
+        };
+        let pass = |search: &Search<Shuttle>| {
+            let root = &search.tree[search.tree.root()];
+            search.tree[root.actions()[0].ptr()].clone()
+        };
+
+        // The position after passing was played before the root, so passing draws at once.
+        let mut search = Search::new(pos.clone(), 1_000);
+        search.set_history(vec![1]);
+        let mov = search.run(
+            Some(pos.clone()),
+            &settings,
+            &SearchParameter::default(),
+            &AtomicBool::new(false),
+            false,
+        );
+
+        assert_eq!(mov, 0);
+        assert_eq!(pass(&search).q(0), 0.0);
+        assert!(pass(&search).is_not_expanded());
+
+        // Without that history the node is searched on instead of staying a draw, where the
+        // opponent tries resigning at least once.
+        search.set_history(Vec::new());
+        search.run(
+            Some(pos),
+            &settings,
+            &SearchParameter::default(),
+            &AtomicBool::new(false),
+            false,
+        );
+
+        assert!(!pass(&search).is_terminal());
+        assert!(!pass(&search).is_not_expanded());
+        assert!(pass(&search).q(0) > 0.0);
     }
 }
@@ -0,0 +1,283 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
+};
+
+use super::{params::SearchParameter, settings::SearchSettings};
+use crate::{
+    tree::{edge::Edge, Tree},
+    Game, GameState,
+};
+
+/// Trees an information set search keeps.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Observer {
+    /// A single tree from the view of the player to move at the root, which sees every move.
+    #[default]
+    Single,
+    /// A tree for every player, which sees the moves of the others as `Game::observe` shows them.
+    Multiple,
+}
+
+/// Search for games with hidden information. Every iteration draws a determinization of what
+/// the player to move at the root can't see and descends the trees along the moves that are
+/// legal in it, so that every node stands for an information set instead of a position.
+pub struct InformationSetSearch<G: Game> {
+    root: G,
+    trees: Vec<Tree>,
+    capacity: usize,
+    observer: Observer,
+    /// State of the xorshift generator drawing determinizations and outcomes of chance nodes.
+    seed: u64,
+}
+
+impl<G: Game> InformationSetSearch<G> {
+    pub fn new(pos: G, capacity: usize, observer: Observer) -> Self {
+        InformationSetSearch {
+            root: pos,
+            trees: Vec::new(),
+            capacity,
+            observer,
+            seed: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    pub fn run(
+        &mut self,
+        pos: Option<G>,
+        settings: &SearchSettings,
+        params: &SearchParameter,
+        abort: &AtomicBool,
+    ) -> G::Move {
+        let timer = Instant::now();
+
+        if let Some(pos) = pos {
+            self.root = pos;
+        }
+
+        let trees = match self.observer {
+            Observer::Single => 1,
+            Observer::Multiple => G::PLAYERS,
+        };
+        self.trees = (0..trees).map(|_| Tree::new(self.capacity)).collect();
+        for tree in &mut self.trees {
+            tree.reset(&self.root);
+        }
+
+        let mut nodes = 0;
+        loop {
+            self.execute_iteration(params);
+            nodes += 1;
+
+            if nodes >= settings.max_nodes || abort.load(Ordering::Relaxed) {
+                break;
+            }
+
+            match settings.max_time {
+                Some(time) if timer.elapsed().as_millis() >= time => break,
+                _ => continue,
+            };
+        }
+
+        let tree = self.root_tree();
+        tree[tree.root()]
+            .actions()
+            .iter()
+            .filter(|edge| edge.ptr() != -1)
+            .max_by(|a, b| tree[a.ptr()].visits().total_cmp(&tree[b.ptr()].visits()))
+            .map(|edge| edge.mov())
+            .unwrap()
+            .into()
+    }
+
+    /// Average value of the root from the perspective of its side to move.
+    pub fn root_q(&self) -> f32 {
+        let tree = self.root_tree();
+        tree[tree.root()].q(self.root.side_to_move())
+    }
+
+    fn root_tree(&self) -> &Tree {
+        &self.trees[self.tree_of(self.root.side_to_move())]
+    }
+
+    fn tree_of(&self, player: usize) -> usize {
+        match self.observer {
+            Observer::Single => 0,
+            Observer::Multiple => player,
+        }
+    }
+
+    fn execute_iteration(&mut self, params: &SearchParameter) {
+        let seed = self.next_seed();
+        let mut pos = self.root.determinize(self.root.side_to_move(), seed);
+
+        let mut paths: Vec<Vec<i32>> = self.trees.iter().map(|tree| vec![tree.root()]).collect();
+
+        while pos.game_state() == GameState::Ongoing {
+            let mover = self.tree_of(pos.side_to_move());
+            let index = *paths[mover].last().unwrap();
+
+            let mov = if pos.is_chance() {
+                self.sample_outcome(&pos)
+            } else {
+                self.select(mover, index, &mut pos, params)
+            };
+
+            let observed: Vec<u32> = (0..self.trees.len())
+                .map(|tree| match self.observer {
+                    Observer::Single => mov.into(),
+                    Observer::Multiple => pos.observe(mov, tree).into(),
+                })
+                .collect();
+
+            pos.make_move(mov);
+
+            // The iteration ends once the tree of the player who moved grows by a node.
+            let mut expanded = false;
+            for (tree, path) in paths.iter_mut().enumerate() {
+                let index = *path.last().unwrap();
+                let (child, added) = self.child(tree, index, observed[tree], &pos);
+
+                path.push(child);
+                expanded |= added && tree == mover;
+            }
+
+            if expanded {
+                break;
+            }
+        }
+
+        let rewards = match pos.game_state() {
+            GameState::Ongoing => G::get_rewards(&mut [pos])[0],
+            state => state.rewards(pos.side_to_move()),
+        };
+
+        for (tree, path) in self.trees.iter_mut().zip(&paths) {
+            for &index in path {
+                tree.propagate(index, &rewards);
+            }
+        }
+    }
+
+    /// Picks among the moves legal in the determinization, adding edges for those which
+    /// weren't legal in any before. Untried moves go first, the others are weighted by how
+    /// often they were available instead of by the visits of the node.
+    fn select(
+        &mut self,
+        tree: usize,
+        index: i32,
+        pos: &mut G,
+        params: &SearchParameter,
+    ) -> G::Move {
+        let moves = pos.get_legal_moves();
+        let policies = pos.get_policy(&moves);
+
+        let tree = &mut self.trees[tree];
+        let node = &mut tree[index];
+
+        let mut legal = Vec::with_capacity(moves.len());
+        for (mov, policy) in moves.iter().zip(policies) {
+            let mov = (*mov).into();
+
+            let edge = match node.actions().iter().position(|edge| edge.mov() == mov) {
+                Some(edge) => edge,
+                None => {
+                    let mut edge = Edge::new(mov);
+                    edge.set_policy(policy);
+                    node.actions_mut().push(edge);
+
+                    node.actions().len() - 1
+                }
+            };
+
+            legal.push(edge);
+        }
+
+        let node = &tree[index];
+        let player = node.side_to_move();
+        let cpuct = params.cpuct(node);
+
+        for &edge in &legal {
+            let ptr = tree.edge(index, edge).ptr();
+            if ptr != -1 {
+                tree[ptr].add_availability();
+            }
+        }
+
+        let node = &tree[index];
+        if let Some(&edge) = legal.iter().find(|&&edge| node.actions()[edge].ptr() == -1) {
+            return tree.edge(index, edge).mov().into();
+        }
+
+        let mut best = legal[0];
+        let mut max = f32::NEG_INFINITY;
+
+        for &edge in &legal {
+            let action = tree.edge(index, edge);
+            let child = &tree[action.ptr()];
+
+            let u = cpuct * child.availability().sqrt() * action.policy() / (1.0 + child.visits());
+            let uct = child.q(player) + u;
+
+            if max < uct {
+                best = edge;
+                max = uct;
+            }
+        }
+
+        tree.edge(index, best).mov().into()
+    }
+
+    /// Follows the edge with the move from the node, adding the edge and its child if they don't
+    /// exist yet. Returns the child and whether it was added.
+    fn child(&mut self, tree: usize, index: i32, mov: u32, pos: &G) -> (i32, bool) {
+        let tree = &mut self.trees[tree];
+
+        let edge = match tree[index]
+            .actions()
+            .iter()
+            .position(|edge| edge.mov() == mov)
+        {
+            Some(edge) => edge,
+            None => {
+                tree[index].actions_mut().push(Edge::new(mov));
+                tree[index].actions().len() - 1
+            }
+        };
+
+        let ptr = tree.edge(index, edge).ptr();
+        if ptr != -1 {
+            return (ptr, false);
+        }
+
+        // Information sets aren't terminal by themselves, as the state is read from the
+        // determinization of every iteration.
+        let child = tree.add(pos, GameState::Ongoing, index);
+        tree.edge_mut(index, edge).set_ptr(child);
+
+        (child, true)
+    }
+
+    fn sample_outcome(&mut self, pos: &G) -> G::Move {
+        let outcomes = pos.get_outcomes();
+        let total: f32 = outcomes.iter().map(|(_, p)| p).sum();
+        let mut sample = (self.next_seed() >> 40) as f32 / (1u64 << 24) as f32 * total;
+
+        for &(mov, p) in &outcomes {
+            sample -= p;
+            if sample < 0.0 {
+                return mov;
+            }
+        }
+
+        outcomes[outcomes.len() - 1].0
+    }
+
+    fn next_seed(&mut self) -> u64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+
+        self.seed
+    }
+}
@@ -1,9 +1,20 @@
 use crate::tree::node::Node;
 
+/// Whose rewards a player maximises in games with more than two players.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Strategy {
+    /// Every player maximises its own reward.
+    #[default]
+    MaxN,
+    /// Every other player minimises the reward of the player to move at the root.
+    Paranoid,
+}
+
 #[derive(Copy, Clone)]
 pub struct SearchParameter {
     pub cpuct_init: f32,
     pub cpuct_base: f32,
+    pub strategy: Strategy,
 }
 
 impl Default for SearchParameter {
@@ -11,6 +22,7 @@ impl Default for SearchParameter {
         SearchParameter {
             cpuct_init: 1.41,
             cpuct_base: 1.0,
+            strategy: Strategy::MaxN,
         }
     }
 }
@@ -20,6 +32,7 @@ impl SearchParameter {
         SearchParameter {
             cpuct_init,
             cpuct_base,
+            strategy: Strategy::MaxN,
         }
     }
 
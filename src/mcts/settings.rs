@@ -2,4 +2,5 @@
 pub struct SearchSettings {
     pub max_time: Option<u128>,
     pub max_nodes: usize,
+    pub batch_size: usize,
 }
@@ -1,3 +1,6 @@
+pub mod accumulator;
 pub mod activation;
 pub mod layer;
+pub mod quantized;
+pub mod simd;
 pub mod vector;
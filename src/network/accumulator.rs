@@ -0,0 +1,102 @@
+use std::io::{self, Read};
+
+use super::{activation::Activation, layer::DenseLayer, vector::Vector};
+
+/// Output of a [`FeatureTransformer`] before activation, kept up to date as features change.
+#[derive(Copy, Clone)]
+pub struct Accumulator<const M: usize>(pub Vector<f32, M>);
+
+impl<const M: usize> Accumulator<M> {
+    pub fn activate<T: Activation>(&self) -> Vector<f32, M> {
+        self.0.activate::<T>()
+    }
+}
+
+/// First layer of a network over sparse binary inputs. The weights are stored per feature,
+/// so adding or removing a feature only touches a single contiguous column.
+pub struct FeatureTransformer<const N: usize, const M: usize> {
+    weights: [Vector<f32, M>; N],
+    bias: Vector<f32, M>,
+}
+
+impl<const N: usize, const M: usize> FeatureTransformer<N, M> {
+    /// Reads the weights in the layout of a [`DenseLayer`].
+    pub fn read<R: Read>(reader: &mut R) -> io::Result<Box<Self>> {
+        Ok(Self::from_dense(&*DenseLayer::read(reader)?))
+    }
+
+    pub fn from_dense(layer: &DenseLayer<f32, N, M>) -> Box<Self> {
+        // SAFETY: The all-zero bit pattern is a valid `f32`.
+        let mut transformer = unsafe { Box::<Self>::new_zeroed().assume_init() };
+
+        for (output, weights) in layer.weights().iter().enumerate() {
+            for (feature, weight) in weights.0.iter().enumerate() {
+                transformer.weights[feature].0[output] = *weight;
+            }
+        }
+
+        transformer.bias = *layer.bias();
+        transformer
+    }
+
+    pub fn refresh(&self, features: &[usize]) -> Accumulator<M> {
+        let mut accumulator = Accumulator(self.bias);
+
+        for &feature in features {
+            self.add(&mut accumulator, feature);
+        }
+
+        accumulator
+    }
+
+    #[inline]
+    pub fn add(&self, accumulator: &mut Accumulator<M>, feature: usize) {
+        for (value, weight) in accumulator.0 .0.iter_mut().zip(self.weights[feature].0) {
+            *value += weight;
+        }
+    }
+
+    #[inline]
+    pub fn remove(&self, accumulator: &mut Accumulator<M>, feature: usize) {
+        for (value, weight) in accumulator.0 .0.iter_mut().zip(self.weights[feature].0) {
+            *value -= weight;
+        }
+    }
+}
+
+#[cfg(test)]
+mod accumulator_test {
+    use super::FeatureTransformer;
+    use crate::network::{activation::ReLu, layer::DenseLayer, simd::dot_scalar, vector::Vector};
+
+    const N: usize = 96;
+    const M: usize = 24;
+
+    #[test]
+    fn assert_refresh_matches_dense() {
+        let mut bytes = Vec::new();
+        for i in 0..(N + 1) * M {
+            bytes.extend_from_slice(&((i as f32 * 0.7).sin()).to_le_bytes());
+        }
+
+        let layer = DenseLayer::<f32, N, M>::read(&mut bytes.as_slice()).unwrap();
+        let transformer = FeatureTransformer::from_dense(&layer);
+
+        let features = [3, 17, 42, 95];
+        let mut input = Vector([0.0; N]);
+        for feature in features {
+            input.0[feature] = 1.0;
+        }
+
+        let mut accumulator = transformer.refresh(&[3, 17, 50]);
+        transformer.remove(&mut accumulator, 50);
+        transformer.add(&mut accumulator, 42);
+        transformer.add(&mut accumulator, 95);
+
+        for (i, actual) in accumulator.activate::<ReLu>().into_iter().enumerate() {
+            let expected =
+                (dot_scalar(&layer.weights()[i].0, &input.0) + layer.bias().0[i]).max(0.0);
+            assert!((expected - actual).abs() < 1e-4);
+        }
+    }
+}
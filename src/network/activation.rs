@@ -3,6 +3,7 @@
 pub struct Sigmoid;
 pub struct ReLu;
 pub struct TanH;
+pub struct Identity;
 
 pub trait Activation {
     fn activate(x: f32) -> f32;
@@ -25,3 +26,19 @@ impl Activation for TanH {
         x.tanh()
     }
 }
+
+impl Activation for Identity {
+    fn activate(x: f32) -> f32 {
+        x
+    }
+}
+
+/// Normalises logits into a probability distribution.
+pub fn softmax(logits: &[f32]) -> Vec<f32> {
+    let max = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+    let exps: Vec<f32> = logits.iter().map(|&x| (x - max).exp()).collect();
+    let sum: f32 = exps.iter().sum();
+
+    exps.iter().map(|&x| x / sum).collect()
+}
@@ -1,4 +1,6 @@
-use super::{activation::Activation, vector::Vector};
+use std::io::{self, Read, Write};
+
+use super::{activation::Activation, quantized::read_f32, simd, vector::Vector};
 
 pub trait Layer {
     type InputType: Clone + Copy;
@@ -12,17 +14,131 @@ pub struct DenseLayer<T, const N: usize, const M: usize> {
     bias: Vector<T, M>,
 }
 
+impl<T, const N: usize, const M: usize> DenseLayer<T, N, M> {
+    pub fn new(weights: [Vector<T, N>; M], bias: Vector<T, M>) -> Self {
+        DenseLayer { weights, bias }
+    }
+
+    pub fn weights(&self) -> &[Vector<T, N>; M] {
+        &self.weights
+    }
+
+    pub fn bias(&self) -> &Vector<T, M> {
+        &self.bias
+    }
+}
+
+impl<const N: usize, const M: usize> DenseLayer<f32, N, M> {
+    /// Reads the little-endian weights row by row, followed by the bias.
+    pub fn read<R: Read>(reader: &mut R) -> io::Result<Box<Self>> {
+        // SAFETY: The all-zero bit pattern is a valid `f32`.
+        let mut layer = unsafe { Box::<Self>::new_zeroed().assume_init() };
+
+        for weights in layer.weights.iter_mut() {
+            for weight in weights.0.iter_mut() {
+                *weight = read_f32(reader)?;
+            }
+        }
+
+        for bias in layer.bias.0.iter_mut() {
+            *bias = read_f32(reader)?;
+        }
+
+        Ok(layer)
+    }
+
+    pub fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for weights in self.weights.iter() {
+            for weight in weights.0 {
+                writer.write_all(&weight.to_le_bytes())?;
+            }
+        }
+
+        for bias in self.bias.0 {
+            writer.write_all(&bias.to_le_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// Same as `forward` on every input, but each row of weights is loaded once for the whole
+    /// batch.
+    pub fn forward_batch<T: Activation>(&self, inputs: &[Vector<f32, N>]) -> Vec<Vector<f32, M>> {
+        let dot = simd::dot_kernel();
+        let mut outputs = vec![self.bias; inputs.len()];
+
+        for (i, weights) in self.weights.iter().enumerate() {
+            for (output, input) in outputs.iter_mut().zip(inputs) {
+                output.0[i] = T::activate(dot(&weights.0, &input.0) + output.0[i]);
+            }
+        }
+
+        outputs
+    }
+}
+
 impl<const N: usize, const M: usize> Layer for DenseLayer<f32, N, M> {
     type InputType = Vector<f32, N>;
     type OutputType = Vector<f32, M>;
 
     fn forward<T: Activation>(&self, input: Self::InputType) -> Self::OutputType {
+        let dot = simd::dot_kernel();
         let mut activations = self.bias;
 
         for (activation, weights) in activations.0.iter_mut().zip(self.weights.iter()) {
-            *activation = T::activate(weights.dot(&input) + *activation);
+            *activation = T::activate(dot(&weights.0, &input.0) + *activation);
         }
 
         activations
     }
 }
+
+#[cfg(test)]
+mod layer_test {
+    use super::{DenseLayer, Layer};
+    use crate::network::{activation::TanH, simd::dot_scalar, vector::Vector};
+
+    const N: usize = 37;
+    const M: usize = 11;
+
+    #[test]
+    fn assert_forward_matches_scalar() {
+        let weights: [Vector<f32, N>; M] =
+            std::array::from_fn(|i| Vector(std::array::from_fn(|j| ((i * N + j) as f32).sin())));
+        let bias = Vector(std::array::from_fn(|i| (i as f32).cos()));
+        let input = Vector(std::array::from_fn(|i| (i as f32 * 0.37).sin()));
+
+        let layer = DenseLayer::new(weights, bias);
+        let output = layer.forward::<TanH>(input);
+
+        for (i, actual) in output.into_iter().enumerate() {
+            let expected = (dot_scalar(&weights[i].0, &input.0) + bias.0[i]).tanh();
+
+            assert!(
+                (expected - actual).abs() < 1e-5,
+                "Output {} differs: {} != {}",
+                i,
+                expected,
+                actual
+            );
+        }
+    }
+
+    #[test]
+    fn assert_batch_matches_forward() {
+        let weights: [Vector<f32, N>; M] =
+            std::array::from_fn(|i| Vector(std::array::from_fn(|j| ((i * N + j) as f32).cos())));
+        let bias = Vector(std::array::from_fn(|i| (i as f32).sin()));
+        let inputs: Vec<Vector<f32, N>> = (0..5)
+            .map(|k| Vector(std::array::from_fn(|i| ((i + k * N) as f32 * 0.23).sin())))
+            .collect();
+
+        let layer = DenseLayer::new(weights, bias);
+        let outputs = layer.forward_batch::<TanH>(&inputs);
+
+        assert_eq!(outputs.len(), inputs.len());
+        for (input, output) in inputs.into_iter().zip(outputs) {
+            assert_eq!(layer.forward::<TanH>(input).0, output.0);
+        }
+    }
+}
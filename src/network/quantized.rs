@@ -0,0 +1,324 @@
+use std::io::{self, Read, Write};
+
+use super::{
+    activation::Activation,
+    layer::{DenseLayer, Layer},
+    simd,
+    vector::Vector,
+};
+
+/// Fixed-point scale of the `i16` activations passed between quantized layers.
+pub const ACTIVATION_SCALE: f32 = 255.0;
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for i8 {}
+    impl Sealed for i16 {}
+}
+
+/// Integer weight type of a [`QuantizedLayer`]. Products are always accumulated in `i32`.
+pub trait Quantized: private::Sealed + Copy + Default {
+    /// Largest magnitude a weight is mapped to. The inputs of a layer are clamped to
+    /// [`QuantizedLayer::MAX_INPUT`] to keep its dot products within `i32`.
+    const LIMIT: f32;
+
+    fn quantize(x: f32) -> Self;
+
+    fn dot_kernel() -> fn(&[Self], &[i16]) -> i32;
+
+    fn read<R: Read>(reader: &mut R) -> io::Result<Self>;
+
+    fn write<W: Write>(self, writer: &mut W) -> io::Result<()>;
+}
+
+impl Quantized for i16 {
+    const LIMIT: f32 = 4096.0;
+
+    fn quantize(x: f32) -> Self {
+        x.round().clamp(-Self::LIMIT, Self::LIMIT) as i16
+    }
+
+    #[inline]
+    fn dot_kernel() -> fn(&[Self], &[i16]) -> i32 {
+        simd::dot_i16_kernel()
+    }
+
+    fn read<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut bytes = [0; 2];
+        reader.read_exact(&mut bytes)?;
+
+        Ok(i16::from_le_bytes(bytes))
+    }
+
+    fn write<W: Write>(self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.to_le_bytes())
+    }
+}
+
+impl Quantized for i8 {
+    const LIMIT: f32 = 127.0;
+
+    fn quantize(x: f32) -> Self {
+        x.round().clamp(-Self::LIMIT, Self::LIMIT) as i8
+    }
+
+    #[inline]
+    fn dot_kernel() -> fn(&[Self], &[i16]) -> i32 {
+        simd::dot_i8_kernel()
+    }
+
+    fn read<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut bytes = [0; 1];
+        reader.read_exact(&mut bytes)?;
+
+        Ok(i8::from_le_bytes(bytes))
+    }
+
+    fn write<W: Write>(self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.to_le_bytes())
+    }
+}
+
+#[inline]
+pub fn quantize_activation(x: f32) -> i16 {
+    // Rounds half away from zero without `f32::round`, which is a libm call on baseline x86-64,
+    // while the saturating cast takes care of the clamping.
+    let x = x * ACTIVATION_SCALE;
+    (x + 0.5f32.copysign(x)) as i16
+}
+
+pub fn dequantize_activation(x: i16) -> f32 {
+    f32::from(x) / ACTIVATION_SCALE
+}
+
+/// Integer counterpart of [`DenseLayer`] with a single scale for all weights of the layer.
+pub struct QuantizedLayer<T, const N: usize, const M: usize> {
+    weights: [Vector<T, N>; M],
+    bias: Vector<i32, M>,
+    scale: f32,
+}
+
+impl<T: Quantized, const N: usize, const M: usize> QuantizedLayer<T, N, M> {
+    /// Largest input activation for which a dot product over `N` weights of up to `T::LIMIT`
+    /// takes at most half the range of `i32`, which leaves the other half to the bias.
+    pub const MAX_INPUT: i16 = {
+        let max = (i32::MAX / 2) as i64 / (N as i64 * T::LIMIT as i64);
+        if max < i16::MAX as i64 {
+            max as i16
+        } else {
+            i16::MAX
+        }
+    };
+
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Applies the layer and returns the activated outputs without requantizing them,
+    /// which keeps the full precision for the last layer of a network.
+    pub fn forward_f32<A: Activation>(&self, input: &Vector<i16, N>) -> Vector<f32, M> {
+        let dot = T::dot_kernel();
+        let dequantize = 1.0 / (self.scale * ACTIVATION_SCALE);
+        let mut activations = Vector([0f32; M]);
+
+        let input = input.0.map(|x| x.clamp(-Self::MAX_INPUT, Self::MAX_INPUT));
+
+        for ((activation, weights), bias) in activations
+            .0
+            .iter_mut()
+            .zip(self.weights.iter())
+            .zip(self.bias.0.iter())
+        {
+            let sum = dot(&weights.0, &input).saturating_add(*bias);
+            *activation = A::activate(sum as f32 * dequantize);
+        }
+
+        activations
+    }
+
+    pub fn read<R: Read>(reader: &mut R) -> io::Result<Box<Self>> {
+        // SAFETY: `Quantized` is sealed to integer types, so together with the `i32` bias and
+        // the `f32` scale every field is valid for the all-zero bit pattern.
+        let mut layer = unsafe { Box::<Self>::new_zeroed().assume_init() };
+
+        layer.scale = read_f32(reader)?;
+        for weights in layer.weights.iter_mut() {
+            for weight in weights.0.iter_mut() {
+                *weight = T::read(reader)?;
+            }
+        }
+
+        for bias in layer.bias.0.iter_mut() {
+            let mut bytes = [0; 4];
+            reader.read_exact(&mut bytes)?;
+
+            *bias = i32::from_le_bytes(bytes);
+        }
+
+        Ok(layer)
+    }
+
+    pub fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.scale.to_le_bytes())?;
+        for weights in self.weights.iter() {
+            for weight in weights.0 {
+                weight.write(writer)?;
+            }
+        }
+
+        for bias in self.bias.0 {
+            writer.write_all(&bias.to_le_bytes())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Quantized, const N: usize, const M: usize> Layer for QuantizedLayer<T, N, M> {
+    type InputType = Vector<i16, N>;
+    type OutputType = Vector<i16, M>;
+
+    fn forward<A: Activation>(&self, input: Self::InputType) -> Self::OutputType {
+        Vector(self.forward_f32::<A>(&input).0.map(quantize_activation))
+    }
+}
+
+impl<const N: usize, const M: usize> DenseLayer<f32, N, M> {
+    /// Maps the weights onto `T` with a per-layer scale chosen from the largest weight.
+    pub fn quantize<T: Quantized>(&self) -> Box<QuantizedLayer<T, N, M>> {
+        let max = self
+            .weights()
+            .iter()
+            .flat_map(|weights| weights.0.iter())
+            .fold(0f32, |max, weight| max.max(weight.abs()));
+
+        let scale = if max > 0.0 { T::LIMIT / max } else { 1.0 };
+
+        // SAFETY: See `QuantizedLayer::read`.
+        let mut layer = unsafe { Box::<QuantizedLayer<T, N, M>>::new_zeroed().assume_init() };
+
+        layer.scale = scale;
+        for (quantized, weights) in layer.weights.iter_mut().zip(self.weights().iter()) {
+            for (quantized, weight) in quantized.0.iter_mut().zip(weights.0) {
+                *quantized = T::quantize(weight * scale);
+            }
+        }
+
+        for (quantized, bias) in layer.bias.0.iter_mut().zip(self.bias().0) {
+            *quantized = (bias * scale * ACTIVATION_SCALE).round() as i32;
+        }
+
+        layer
+    }
+}
+
+pub(super) fn read_f32<R: Read>(reader: &mut R) -> io::Result<f32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+
+    Ok(f32::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod quantized_test {
+    use super::{dequantize_activation, QuantizedLayer, ACTIVATION_SCALE};
+    use crate::network::{
+        activation::{Identity, ReLu, TanH},
+        layer::{DenseLayer, Layer},
+        vector::Vector,
+    };
+
+    const N: usize = 64;
+    const M: usize = 16;
+
+    fn layer() -> Box<DenseLayer<f32, N, M>> {
+        let mut bytes = Vec::new();
+        for i in 0..N * M {
+            bytes.extend_from_slice(&((i as f32 * 0.7).sin() * 0.5).to_le_bytes());
+        }
+
+        for i in 0..M {
+            bytes.extend_from_slice(&((i as f32).cos() * 0.1).to_le_bytes());
+        }
+
+        DenseLayer::read(&mut bytes.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn assert_quantized_matches_float() {
+        let layer = layer();
+        let input = Vector(std::array::from_fn(|i| (i as f32 * 0.37).sin().max(0.0)));
+
+        let expected = layer.forward::<TanH>(input);
+        let i16_output = layer
+            .quantize::<i16>()
+            .forward_f32::<TanH>(&input.quantize());
+        let i8_output = layer
+            .quantize::<i8>()
+            .forward_f32::<TanH>(&input.quantize());
+
+        for i in 0..M {
+            assert!((expected.0[i] - i16_output.0[i]).abs() < 0.01);
+            assert!((expected.0[i] - i8_output.0[i]).abs() < 0.05);
+        }
+
+        let hidden = layer.quantize::<i16>().forward::<ReLu>(input.quantize());
+        for (expected, actual) in layer.forward::<ReLu>(input).into_iter().zip(hidden) {
+            assert!((expected - dequantize_activation(actual)).abs() < 2.0 / ACTIVATION_SCALE);
+        }
+    }
+
+    #[test]
+    fn assert_saturated_inputs() {
+        // Weights of the largest magnitude with the same sign sum up the most.
+        let mut bytes = Vec::new();
+        for _ in 0..N * M {
+            bytes.extend_from_slice(&0.5f32.to_le_bytes());
+        }
+        bytes.extend_from_slice(&[0; 4 * M]);
+
+        let layer = DenseLayer::<f32, N, M>::read(&mut bytes.as_slice()).unwrap();
+
+        let quantized = layer.quantize::<i16>();
+        let bound = QuantizedLayer::<i16, N, M>::MAX_INPUT;
+        assert!(i64::from(bound) * N as i64 * 4096 <= i64::from(i32::MAX));
+
+        // Inputs beyond the bound are clamped instead of overflowing into sums of the wrong sign.
+        for input in [i16::MAX, i16::MIN] {
+            let saturated = quantized.forward_f32::<Identity>(&Vector([input; N]));
+            let clamped = quantized.forward_f32::<Identity>(&Vector([bound * input.signum(); N]));
+
+            assert_eq!(saturated.0, clamped.0);
+            assert!(saturated
+                .0
+                .iter()
+                .all(|&x| x.signum() == f32::from(input.signum())));
+        }
+
+        // With `i8` weights even saturated inputs fit.
+        let bound = QuantizedLayer::<i8, N, M>::MAX_INPUT;
+        let saturated = layer
+            .quantize::<i8>()
+            .forward_f32::<Identity>(&Vector([i16::MAX; N]));
+        assert_eq!(bound, i16::MAX);
+        assert!(saturated.0.iter().all(|&x| x > 0.0));
+    }
+
+    #[test]
+    fn assert_write_read_roundtrip() {
+        let quantized = layer().quantize::<i8>();
+
+        let mut bytes = Vec::new();
+        quantized.write(&mut bytes).unwrap();
+
+        let restored = QuantizedLayer::<i8, N, M>::read(&mut bytes.as_slice()).unwrap();
+        let input = Vector(std::array::from_fn(|i| i as i16));
+
+        assert_eq!(quantized.scale(), restored.scale());
+        assert_eq!(
+            quantized.forward::<ReLu>(input).0,
+            restored.forward::<ReLu>(input).0
+        );
+    }
+}
@@ -0,0 +1,388 @@
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+pub type DotKernel = fn(&[f32], &[f32]) -> f32;
+pub type DotI16Kernel = fn(&[i16], &[i16]) -> i32;
+pub type DotI8Kernel = fn(&[i8], &[i16]) -> i32;
+
+/// Picks the fastest dot product supported by the running CPU.
+#[inline]
+pub fn dot_kernel() -> DotKernel {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            return dot_avx2_fma;
+        }
+
+        if is_x86_feature_detected!("avx2") {
+            return dot_avx2;
+        }
+
+        if is_x86_feature_detected!("sse") {
+            return dot_sse;
+        }
+    }
+
+    dot_scalar
+}
+
+/// Lists every kernel the running CPU can execute, scalar first.
+pub fn available_kernels() -> Vec<(&'static str, DotKernel)> {
+    #[allow(unused_mut)]
+    let mut kernels: Vec<(&'static str, DotKernel)> = vec![("scalar", dot_scalar)];
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("sse") {
+            kernels.push(("sse", dot_sse));
+        }
+
+        if is_x86_feature_detected!("avx2") {
+            kernels.push(("avx2", dot_avx2));
+        }
+
+        if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            kernels.push(("avx2+fma", dot_avx2_fma));
+        }
+    }
+
+    kernels
+}
+
+/// Picks the fastest `i16` dot product with `i32` accumulation for the running CPU.
+#[inline]
+pub fn dot_i16_kernel() -> DotI16Kernel {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") {
+            return dot_i16_avx2;
+        }
+
+        if is_x86_feature_detected!("sse2") {
+            return dot_i16_sse2;
+        }
+    }
+
+    dot_i16_scalar
+}
+
+/// Picks the fastest `i8` weight by `i16` input dot product for the running CPU.
+#[inline]
+pub fn dot_i8_kernel() -> DotI8Kernel {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") {
+            return dot_i8_avx2;
+        }
+    }
+
+    dot_i8_scalar
+}
+
+pub fn available_i16_kernels() -> Vec<(&'static str, DotI16Kernel)> {
+    #[allow(unused_mut)]
+    let mut kernels: Vec<(&'static str, DotI16Kernel)> = vec![("scalar", dot_i16_scalar)];
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("sse2") {
+            kernels.push(("sse2", dot_i16_sse2));
+        }
+
+        if is_x86_feature_detected!("avx2") {
+            kernels.push(("avx2", dot_i16_avx2));
+        }
+    }
+
+    kernels
+}
+
+pub fn available_i8_kernels() -> Vec<(&'static str, DotI8Kernel)> {
+    #[allow(unused_mut)]
+    let mut kernels: Vec<(&'static str, DotI8Kernel)> = vec![("scalar", dot_i8_scalar)];
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") {
+            kernels.push(("avx2", dot_i8_avx2));
+        }
+    }
+
+    kernels
+}
+
+pub fn dot_scalar(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(a, b)| a * b).sum()
+}
+
+pub fn dot_i16_scalar(a: &[i16], b: &[i16]) -> i32 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(&a, &b)| i32::from(a) * i32::from(b))
+        .sum()
+}
+
+pub fn dot_i8_scalar(a: &[i8], b: &[i16]) -> i32 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(&a, &b)| i32::from(a) * i32::from(b))
+        .sum()
+}
+
+#[cfg(target_arch = "x86_64")]
+fn dot_sse(a: &[f32], b: &[f32]) -> f32 {
+    assert_eq!(a.len(), b.len());
+
+    // SAFETY: Only handed out after runtime detection of SSE.
+    unsafe { sse_impl(a, b) }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn dot_avx2(a: &[f32], b: &[f32]) -> f32 {
+    assert_eq!(a.len(), b.len());
+
+    // SAFETY: Only handed out after runtime detection of AVX2.
+    unsafe { avx2_impl(a, b) }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn dot_avx2_fma(a: &[f32], b: &[f32]) -> f32 {
+    assert_eq!(a.len(), b.len());
+
+    // SAFETY: Only handed out after runtime detection of AVX2 and FMA.
+    unsafe { avx2_fma_impl(a, b) }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn dot_i16_sse2(a: &[i16], b: &[i16]) -> i32 {
+    assert_eq!(a.len(), b.len());
+
+    // SAFETY: Only handed out after runtime detection of SSE2.
+    unsafe { i16_sse2_impl(a, b) }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn dot_i16_avx2(a: &[i16], b: &[i16]) -> i32 {
+    assert_eq!(a.len(), b.len());
+
+    // SAFETY: Only handed out after runtime detection of AVX2.
+    unsafe { i16_avx2_impl(a, b) }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn dot_i8_avx2(a: &[i8], b: &[i16]) -> i32 {
+    assert_eq!(a.len(), b.len());
+
+    // SAFETY: Only handed out after runtime detection of AVX2.
+    unsafe { i8_avx2_impl(a, b) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse")]
+unsafe fn sse_impl(a: &[f32], b: &[f32]) -> f32 {
+    const LANES: usize = 4;
+
+    let chunks = a.len() / LANES;
+    let mut sum = _mm_setzero_ps();
+
+    for i in 0..chunks {
+        let x = _mm_loadu_ps(a.as_ptr().add(i * LANES));
+        let y = _mm_loadu_ps(b.as_ptr().add(i * LANES));
+        sum = _mm_add_ps(sum, _mm_mul_ps(x, y));
+    }
+
+    let mut lanes = [0f32; LANES];
+    _mm_storeu_ps(lanes.as_mut_ptr(), sum);
+
+    lanes.iter().sum::<f32>() + dot_scalar(&a[chunks * LANES..], &b[chunks * LANES..])
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn avx2_impl(a: &[f32], b: &[f32]) -> f32 {
+    const LANES: usize = 8;
+
+    let chunks = a.len() / LANES;
+    let mut sum = _mm256_setzero_ps();
+
+    for i in 0..chunks {
+        let x = _mm256_loadu_ps(a.as_ptr().add(i * LANES));
+        let y = _mm256_loadu_ps(b.as_ptr().add(i * LANES));
+        sum = _mm256_add_ps(sum, _mm256_mul_ps(x, y));
+    }
+
+    horizontal_sum(sum) + dot_scalar(&a[chunks * LANES..], &b[chunks * LANES..])
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2,fma")]
+unsafe fn avx2_fma_impl(a: &[f32], b: &[f32]) -> f32 {
+    const LANES: usize = 8;
+
+    // Two independent accumulators hide the latency of the fused multiply-add.
+    let chunks = a.len() / (2 * LANES);
+    let mut sum = [_mm256_setzero_ps(); 2];
+
+    for i in 0..chunks {
+        for (j, sum) in sum.iter_mut().enumerate() {
+            let offset = (2 * i + j) * LANES;
+            let x = _mm256_loadu_ps(a.as_ptr().add(offset));
+            let y = _mm256_loadu_ps(b.as_ptr().add(offset));
+            *sum = _mm256_fmadd_ps(x, y, *sum);
+        }
+    }
+
+    let rest = chunks * 2 * LANES;
+    horizontal_sum(_mm256_add_ps(sum[0], sum[1])) + avx2_impl(&a[rest..], &b[rest..])
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn i16_sse2_impl(a: &[i16], b: &[i16]) -> i32 {
+    const LANES: usize = 8;
+
+    let chunks = a.len() / LANES;
+    let mut sum = _mm_setzero_si128();
+
+    for i in 0..chunks {
+        let x = _mm_loadu_si128(a.as_ptr().add(i * LANES).cast());
+        let y = _mm_loadu_si128(b.as_ptr().add(i * LANES).cast());
+        sum = _mm_add_epi32(sum, _mm_madd_epi16(x, y));
+    }
+
+    let mut lanes = [0i32; 4];
+    _mm_storeu_si128(lanes.as_mut_ptr().cast(), sum);
+
+    lanes.iter().sum::<i32>() + dot_i16_scalar(&a[chunks * LANES..], &b[chunks * LANES..])
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn i16_avx2_impl(a: &[i16], b: &[i16]) -> i32 {
+    const LANES: usize = 16;
+
+    let chunks = a.len() / LANES;
+    let mut sum = _mm256_setzero_si256();
+
+    for i in 0..chunks {
+        let x = _mm256_loadu_si256(a.as_ptr().add(i * LANES).cast());
+        let y = _mm256_loadu_si256(b.as_ptr().add(i * LANES).cast());
+        sum = _mm256_add_epi32(sum, _mm256_madd_epi16(x, y));
+    }
+
+    horizontal_sum_i32(sum) + dot_i16_scalar(&a[chunks * LANES..], &b[chunks * LANES..])
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn i8_avx2_impl(a: &[i8], b: &[i16]) -> i32 {
+    const LANES: usize = 16;
+
+    let chunks = a.len() / LANES;
+    let mut sum = _mm256_setzero_si256();
+
+    for i in 0..chunks {
+        let x = _mm256_cvtepi8_epi16(_mm_loadu_si128(a.as_ptr().add(i * LANES).cast()));
+        let y = _mm256_loadu_si256(b.as_ptr().add(i * LANES).cast());
+        sum = _mm256_add_epi32(sum, _mm256_madd_epi16(x, y));
+    }
+
+    horizontal_sum_i32(sum) + dot_i8_scalar(&a[chunks * LANES..], &b[chunks * LANES..])
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn horizontal_sum_i32(x: __m256i) -> i32 {
+    let sum = _mm_add_epi32(_mm256_castsi256_si128(x), _mm256_extracti128_si256(x, 1));
+    let sum = _mm_add_epi32(sum, _mm_unpackhi_epi64(sum, sum));
+    let sum = _mm_add_epi32(sum, _mm_shuffle_epi32(sum, 0b01));
+
+    _mm_cvtsi128_si32(sum)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn horizontal_sum(x: __m256) -> f32 {
+    let sum = _mm_add_ps(_mm256_castps256_ps128(x), _mm256_extractf128_ps(x, 1));
+    let sum = _mm_add_ps(sum, _mm_movehl_ps(sum, sum));
+    let sum = _mm_add_ss(sum, _mm_shuffle_ps(sum, sum, 0b01));
+
+    _mm_cvtss_f32(sum)
+}
+
+#[cfg(test)]
+mod simd_test {
+    use super::{
+        available_i16_kernels, available_i8_kernels, available_kernels, dot_i16_scalar,
+        dot_i8_scalar, dot_scalar,
+    };
+
+    fn sample(len: usize, seed: u32) -> Vec<f32> {
+        let mut state = seed;
+
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (state >> 8) as f32 / (1 << 24) as f32 * 2.0 - 1.0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn assert_kernels_match_scalar() {
+        for len in (0..=67).chain([128, 768, 1024]) {
+            let a = sample(len, 1);
+            let b = sample(len, 2);
+
+            let expected = dot_scalar(&a, &b);
+            let tolerance = 1e-5 * (len as f32).max(1.0);
+
+            for (name, kernel) in available_kernels() {
+                let actual = kernel(&a, &b);
+
+                assert!(
+                    (expected - actual).abs() <= tolerance,
+                    "Kernel {} differs for length {}: {} != {}",
+                    name,
+                    len,
+                    expected,
+                    actual
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn assert_integer_kernels_match_scalar() {
+        for len in (0..=67).chain([128, 768, 1024]) {
+            let a = sample(len, 3);
+            let b = sample(len, 4);
+
+            let weights_i16: Vec<i16> = a.iter().map(|x| (x * 4096.0) as i16).collect();
+            let weights_i8: Vec<i8> = a.iter().map(|x| (x * 127.0) as i8).collect();
+            let input: Vec<i16> = b.iter().map(|x| (x * 255.0) as i16).collect();
+
+            let expected = dot_i16_scalar(&weights_i16, &input);
+            for (name, kernel) in available_i16_kernels() {
+                assert_eq!(
+                    expected,
+                    kernel(&weights_i16, &input),
+                    "Kernel {} differs for length {}",
+                    name,
+                    len
+                );
+            }
+
+            let expected = dot_i8_scalar(&weights_i8, &input);
+            for (name, kernel) in available_i8_kernels() {
+                assert_eq!(
+                    expected,
+                    kernel(&weights_i8, &input),
+                    "Kernel {} differs for length {}",
+                    name,
+                    len
+                );
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::ops::{Add, AddAssign, Mul, MulAssign};
 
-use super::activation::Activation;
+use super::{activation::Activation, quantized, simd};
 
 #[derive(Copy, Clone)]
 pub struct Vector<T, const N: usize>(pub [T; N]);
@@ -15,7 +15,17 @@ impl<const N: usize> Vector<f32, N> {
     }
 
     pub fn dot(&self, other: &Vector<f32, N>) -> f32 {
-        self.0.iter().zip(other.0.iter()).map(|(a, b)| a * b).sum()
+        simd::dot_kernel()(&self.0, &other.0)
+    }
+
+    pub fn quantize(&self) -> Vector<i16, N> {
+        Vector(self.0.map(quantized::quantize_activation))
+    }
+}
+
+impl<const N: usize> Vector<i16, N> {
+    pub fn dequantize(&self) -> Vector<f32, N> {
+        Vector(self.0.map(quantized::dequantize_activation))
     }
 }
 
@@ -2,8 +2,12 @@ pub mod edge;
 pub mod hash;
 pub mod node;
 
-use self::{edge::Edge, hash::HashTable, node::Node};
-use crate::{Game, GameState};
+use self::{
+    edge::Edge,
+    hash::HashTable,
+    node::{Node, NodeKind},
+};
+use crate::{Game, GameState, Rewards, MAX_PLAYERS};
 use std::ops::{Index, IndexMut};
 
 pub struct Tree {
@@ -39,10 +43,22 @@ impl Tree {
         self.root
     }
 
-    pub fn add(&mut self, state: GameState, hash: u64, parent: i32) -> i32 {
+    pub fn add<G: Game>(&mut self, pos: &G, state: GameState, parent: i32) -> i32 {
         let index = self.len();
 
-        self.nodes.push(Node::new(state, hash, parent));
+        let kind = if pos.is_chance() {
+            NodeKind::Chance
+        } else {
+            NodeKind::Decision
+        };
+
+        self.nodes.push(Node::new(
+            state,
+            pos.hash(),
+            kind,
+            pos.side_to_move(),
+            parent,
+        ));
 
         if self.root == -1 {
             self.set_root(index);
@@ -53,7 +69,7 @@ impl Tree {
 
     pub fn subtree<G: Game>(&mut self, root: &G, pos: &Option<G>) {
         if self.is_empty() {
-            self.reset(root);
+            self.reset(pos.as_ref().unwrap_or(root));
             return;
         }
 
@@ -68,20 +84,47 @@ impl Tree {
         }
     }
 
-    pub fn propagate(&mut self, index: i32, reward: f32) {
-        self[index].propagate(reward);
+    /// Adds the rewards to the node. A chance node instead takes the expectation over the
+    /// outcomes visited so far, weighted by their probabilities.
+    pub fn propagate(&mut self, index: i32, rewards: &Rewards) {
+        self[index].propagate(rewards);
+
+        if self[index].is_chance() {
+            if let Some(rewards) = self.expectation(index) {
+                self[index].set_rewards(&rewards);
+            }
+        }
 
         let node = &self[index];
-        self.table.insert(node.hash(), node.visits(), node.value());
+        let value = node.value(node.side_to_move());
+        self.table.insert(node.hash(), node.visits(), value);
     }
 
     pub fn reset<G: Game>(&mut self, pos: &G) {
         self.nodes.clear();
 
-        let node = self.add(GameState::Ongoing, pos.hash(), -1);
+        let node = self.add(pos, GameState::Ongoing, -1);
         self.set_root(node);
     }
 
+    /// Average rewards of the visited outcomes of a chance node.
+    fn expectation(&self, index: i32) -> Option<Rewards> {
+        let (mut sum, mut weight) = ([0.0; MAX_PLAYERS], 0.0);
+
+        for edge in self[index].actions() {
+            if edge.ptr() != -1 && self[edge.ptr()].visits() > 0.0 {
+                let rewards = self[edge.ptr()].rewards();
+
+                for (sum, reward) in sum.iter_mut().zip(rewards) {
+                    *sum += edge.policy() * reward;
+                }
+                weight += edge.policy();
+            }
+        }
+
+        (weight > 0.0).then(|| sum.map(|sum| sum / weight))
+    }
+
     pub fn is_empty(&self) -> bool {
         self.nodes.is_empty()
     }
@@ -113,7 +156,7 @@ impl Tree {
 
         let node = &self[index];
         for action in node.actions() {
-            let mut child = *child;
+            let mut child = child.clone();
             child.make_move(action.mov().into());
 
             let index = self.find(action.ptr(), &child, board, depth - 1);
@@ -1,12 +1,12 @@
 #[derive(Clone, Debug)]
 pub struct Edge {
     ptr: i32,
-    mov: u16,
+    mov: u32,
     policy: u16,
 }
 
 impl Edge {
-    pub fn new(mov: u16) -> Self {
+    pub fn new(mov: u32) -> Self {
         Edge {
             ptr: -1,
             mov,
@@ -18,7 +18,7 @@ impl Edge {
         self.ptr
     }
 
-    pub fn mov(&self) -> u16 {
+    pub fn mov(&self) -> u32 {
         self.mov
     }
 
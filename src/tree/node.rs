@@ -1,25 +1,50 @@
 use super::edge::Edge;
-use crate::{Game, GameState};
+use crate::{Game, GameState, Rewards, MAX_PLAYERS};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NodeKind {
+    /// The side to move chooses the action.
+    Decision,
+    /// The action is drawn from the outcomes of the game, and the value is their expectation.
+    Chance,
+}
 
 #[derive(Clone)]
 pub struct Node {
     parent: i32,
+    kind: NodeKind,
     state: GameState,
     hash: u64,
+    side_to_move: usize,
     actions: Vec<Edge>,
-    value: f32,
+    /// Sum of the rewards of every player.
+    rewards: Rewards,
     visits: f32,
+    virtual_loss: f32,
+    /// Number of times the action leading here was legal, which differs from the visits of the
+    /// parent in an information set tree.
+    availability: f32,
 }
 
 impl Node {
-    pub fn new(state: GameState, hash: u64, parent: i32) -> Self {
+    pub fn new(
+        state: GameState,
+        hash: u64,
+        kind: NodeKind,
+        side_to_move: usize,
+        parent: i32,
+    ) -> Self {
         Node {
             parent,
+            kind,
             state,
             hash,
+            side_to_move,
             actions: Vec::new(),
-            value: 0.0,
+            rewards: [0.0; MAX_PLAYERS],
             visits: 0.0,
+            virtual_loss: 0.0,
+            availability: 0.0,
         }
     }
 
@@ -27,6 +52,14 @@ impl Node {
         self.parent
     }
 
+    pub fn kind(&self) -> NodeKind {
+        self.kind
+    }
+
+    pub fn is_chance(&self) -> bool {
+        self.kind == NodeKind::Chance
+    }
+
     pub fn game_state(&self) -> GameState {
         self.state
     }
@@ -39,6 +72,10 @@ impl Node {
         self.hash
     }
 
+    pub fn side_to_move(&self) -> usize {
+        self.side_to_move
+    }
+
     pub fn actions_mut(&mut self) -> &mut Vec<Edge> {
         &mut self.actions
     }
@@ -51,17 +88,61 @@ impl Node {
         self.visits
     }
 
-    pub fn value(&self) -> f32 {
-        self.value
+    /// Sum of the rewards of `player`.
+    pub fn value(&self, player: usize) -> f32 {
+        self.rewards[player]
+    }
+
+    /// Average reward of `player`.
+    pub fn q(&self, player: usize) -> f32 {
+        self.rewards[player] / self.visits
+    }
+
+    /// Average rewards of every player.
+    pub fn rewards(&self) -> Rewards {
+        self.rewards.map(|reward| reward / self.visits)
+    }
+
+    pub fn virtual_loss(&self) -> f32 {
+        self.virtual_loss
+    }
+
+    pub fn virtual_visits(&self) -> f32 {
+        self.visits + self.virtual_loss
     }
 
-    pub fn q(&self) -> f32 {
-        self.value / self.visits
+    pub fn availability(&self) -> f32 {
+        self.availability
     }
 
+    pub fn add_availability(&mut self) {
+        self.availability += 1.0;
+    }
+
+    pub fn add_virtual_loss(&mut self) {
+        self.virtual_loss += 1.0;
+    }
+
+    pub fn remove_virtual_loss(&mut self) {
+        self.virtual_loss -= 1.0;
+    }
+
+    /// Adds an edge for every legal move, or for every outcome of a chance node with its
+    /// probability in place of the policy.
     pub fn expand<G: Game>(&mut self, pos: &mut G) {
         assert!(self.is_not_expanded());
 
+        if self.is_chance() {
+            for (mov, probability) in pos.get_outcomes() {
+                let mut edge = Edge::new(mov.into());
+                edge.set_policy(probability);
+
+                self.actions.push(edge);
+            }
+
+            return;
+        }
+
         let moves = pos.get_legal_moves();
         let policies = pos.get_policy(&moves);
 
@@ -87,8 +168,16 @@ impl Node {
         self.state != GameState::Ongoing
     }
 
-    pub fn propagate(&mut self, reward: f32) {
+    pub fn propagate(&mut self, rewards: &Rewards) {
         self.visits += 1.0;
-        self.value += reward;
+
+        for (sum, reward) in self.rewards.iter_mut().zip(rewards) {
+            *sum += reward;
+        }
+    }
+
+    /// Replaces the sums of rewards by `visits` times the given averages.
+    pub fn set_rewards(&mut self, rewards: &Rewards) {
+        self.rewards = rewards.map(|reward| reward * self.visits);
     }
 }
@@ -0,0 +1,165 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+};
+
+use games::tune::{self, Tunable};
+
+const BETA1: f32 = 0.9;
+const BETA2: f32 = 0.999;
+const EPSILON: f32 = 1e-8;
+
+struct Options {
+    data: PathBuf,
+    output: PathBuf,
+    epochs: usize,
+    lr: f32,
+}
+
+/// Labelled position reduced to the sparse coefficients of the evaluation.
+struct Entry {
+    coefficients: Vec<(usize, f32)>,
+    label: f32,
+}
+
+pub fn run<T: Tunable>() {
+    let options = parse_args();
+
+    let entries = load::<T>(&options);
+    assert!(!entries.is_empty(), "No positions in {:?}.", options.data);
+
+    println!("info positions {}", entries.len());
+
+    let scale = T::scale();
+    let mut weights = T::parameters();
+    let mut momentum = vec![0.0; weights.len()];
+    let mut velocity = vec![0.0; weights.len()];
+
+    for epoch in 1..=options.epochs {
+        let gradient = gradient(&entries, &weights, scale);
+
+        for (i, weight) in weights.iter_mut().enumerate() {
+            momentum[i] = BETA1 * momentum[i] + (1.0 - BETA1) * gradient[i];
+            velocity[i] = BETA2 * velocity[i] + (1.0 - BETA2) * gradient[i] * gradient[i];
+
+            let momentum = momentum[i] / (1.0 - BETA1.powi(epoch as i32));
+            let velocity = velocity[i] / (1.0 - BETA2.powi(epoch as i32));
+
+            *weight -= options.lr * momentum / (velocity.sqrt() + EPSILON);
+        }
+
+        if epoch % 100 == 0 || epoch == options.epochs {
+            println!(
+                "info epoch {} error {:.6}",
+                epoch,
+                error(&entries, &weights, scale)
+            );
+        }
+    }
+
+    tune::write_parameters(&options.output, scale, &weights, T::group())
+        .expect("Failed to write parameters.");
+}
+
+/// Reads lines of the form `notation | label` as written by the data generator, where the label
+/// is the result from the perspective of the side to move.
+fn load<T: Tunable>(options: &Options) -> Vec<Entry> {
+    let file = File::open(&options.data).expect("Failed to open data file.");
+
+    BufReader::new(file)
+        .lines()
+        .map(|line| line.expect("Failed to read data file."))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (notation, label) = line
+                .rsplit_once('|')
+                .unwrap_or_else(|| panic!("Invalid entry {:?}.", line));
+
+            Entry {
+                coefficients: T::from_notation(notation.trim()).coefficients(),
+                label: label
+                    .trim()
+                    .parse()
+                    .unwrap_or_else(|_| panic!("Invalid label in {:?}.", line)),
+            }
+        })
+        .collect()
+}
+
+fn evaluate(entry: &Entry, weights: &[f32]) -> f32 {
+    entry
+        .coefficients
+        .iter()
+        .map(|&(i, coefficient)| weights[i] * coefficient)
+        .sum()
+}
+
+fn squash(eval: f32, scale: f32) -> f32 {
+    2.0 / (1.0 + (-eval / scale).exp()) - 1.0
+}
+
+/// Mean squared error between the squashed evaluations and the labels.
+fn error(entries: &[Entry], weights: &[f32], scale: f32) -> f32 {
+    let sum: f32 = entries
+        .iter()
+        .map(|entry| (squash(evaluate(entry, weights), scale) - entry.label).powi(2))
+        .sum();
+
+    sum / entries.len() as f32
+}
+
+fn gradient(entries: &[Entry], weights: &[f32], scale: f32) -> Vec<f32> {
+    let mut gradient = vec![0.0; weights.len()];
+
+    for entry in entries {
+        let value = squash(evaluate(entry, weights), scale);
+
+        // Derivative of `(2 * sigmoid(x / scale) - 1 - label)^2` with respect to `x`.
+        let derivative = (value - entry.label) * (1.0 - value * value) / scale;
+
+        for &(i, coefficient) in &entry.coefficients {
+            gradient[i] += derivative * coefficient;
+        }
+    }
+
+    let n = entries.len() as f32;
+    gradient.iter_mut().for_each(|g| *g /= n);
+
+    gradient
+}
+
+fn parse_args() -> Options {
+    let args: Vec<String> = std::env::args().collect();
+    let mut args = args.iter().skip(1);
+
+    let mut data: Option<PathBuf> = None;
+    let mut output: Option<PathBuf> = None;
+    let mut epochs = 1000;
+    let mut lr = 0.1;
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| panic!("Argument for {}.", arg))
+        };
+
+        match arg.as_str() {
+            "--game" => {
+                value();
+            }
+            "--data" => data = Some(PathBuf::from(value())),
+            "--output" => output = Some(PathBuf::from(value())),
+            "--epochs" => epochs = value().parse().expect("Number of epochs."),
+            "--lr" => lr = value().parse().expect("Learning rate."),
+            _ => panic!("Unknown argument {:?}.", arg),
+        }
+    }
+
+    Options {
+        data: data.expect("Argument for data."),
+        output: output.expect("Argument for output."),
+        epochs,
+        lr,
+    }
+}
@@ -0,0 +1,18 @@
+use games::{chess::Chess, connect4::Connect4, registry};
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    match registry::game_arg(&args) {
+        Ok("chess") => tuner::run::<Chess>(),
+        Ok("connect4") => tuner::run::<Connect4>(),
+        Ok(game) => {
+            eprintln!("Game {:?} has no tunable evaluation.", game);
+            std::process::exit(1);
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}